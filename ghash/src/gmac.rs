@@ -0,0 +1,126 @@
+//! GMAC: the authentication-only mode of GCM.
+
+use crate::{Block, GHash, Key, Tag};
+use polyval::universal_hash::{Error, KeyInit, UniversalHash};
+
+/// **GMAC**: the message authentication code used by AES-GCM when there is no
+/// plaintext to encrypt.
+///
+/// Computes `GHASH(H, A || pad || len(A) || 0) XOR E_K(J0)` as described in
+/// [NIST SP 800-38D] Section 7.1, where `A` is the data to be authenticated.
+///
+/// [NIST SP 800-38D]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+#[derive(Clone)]
+pub struct Gmac {
+    ghash: GHash,
+    mask: Block,
+}
+
+impl Gmac {
+    /// Initialize GMAC from the hash subkey `H = E_K(0^128)` and the
+    /// encrypted pre-counter block `E_K(J0)`.
+    pub fn from_subkey(h: &Key, encrypted_j0: &Block) -> Self {
+        Self {
+            ghash: GHash::new(h),
+            mask: *encrypted_j0,
+        }
+    }
+
+    /// Initialize GMAC using the given block cipher and 96-bit nonce.
+    ///
+    /// `encrypt_block` must encrypt a single block in-place under the block
+    /// cipher key `K`, e.g. by calling `BlockCipherEncrypt::encrypt_block` on
+    /// an AES instance.
+    ///
+    /// Derives the hash subkey `H = E_K(0^128)` and the pre-counter block
+    /// `J0 = nonce || 0^31 || 1` as described in NIST SP 800-38D.
+    pub fn new_from_cipher(mut encrypt_block: impl FnMut(&mut Block), nonce: &[u8; 12]) -> Self {
+        let mut h = Key::default();
+        encrypt_block(&mut h);
+
+        let mut j0 = Block::default();
+        j0[..12].copy_from_slice(nonce);
+        j0[15] = 1;
+        encrypt_block(&mut j0);
+
+        let result = Self::from_subkey(&h, &j0);
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+            h.as_mut_slice().zeroize();
+            j0.as_mut_slice().zeroize();
+        }
+
+        result
+    }
+
+    /// Compute the GMAC tag for the given data.
+    #[must_use]
+    pub fn compute(mut self, data: &[u8]) -> Tag {
+        update_with_lengths(&mut self.ghash, data, &[]);
+        self.ghash.clone().finalize_xor(&self.mask)
+    }
+
+    /// Verify the GMAC tag for the given data in constant time.
+    ///
+    /// The result must be checked, as ignoring it would skip authentication:
+    ///
+    /// ```compile_fail
+    /// #![deny(unused_must_use)]
+    /// # use ghash::{Block, Gmac, Key};
+    /// let gmac = Gmac::from_subkey(&Key::default(), &Block::default());
+    /// gmac.verify(b"data", &Block::default());
+    /// ```
+    #[must_use = "ignoring the verification result skips authentication"]
+    pub fn verify(mut self, data: &[u8], expected: &Tag) -> Result<(), Error> {
+        update_with_lengths(&mut self.ghash, data, &[]);
+        let mut expected = *expected;
+        xor(&mut expected, &self.mask);
+        self.ghash.clone().verify(&expected)
+    }
+}
+
+/// Compute the AES-GCM authentication tag for the given additional data and
+/// ciphertext.
+///
+/// Computes `GHASH(H, A || pad || C || pad || len(A) || len(C)) XOR E_K(J0)`
+/// as described in [NIST SP 800-38D] Section 7.1, where `h` is the hash
+/// subkey `H = E_K(0^128)` and `encrypted_j0` is the encrypted pre-counter
+/// block `E_K(J0)`.
+///
+/// [NIST SP 800-38D]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+#[must_use]
+pub fn gmac(h: &Key, encrypted_j0: &Block, aad: &[u8], ciphertext: &[u8]) -> Tag {
+    let mut ghash = GHash::new(h);
+    update_with_lengths(&mut ghash, aad, ciphertext);
+    ghash.finalize_xor(encrypted_j0)
+}
+
+/// Input the given additional data and ciphertext followed by the GCM
+/// lengths block.
+fn update_with_lengths(ghash: &mut GHash, aad: &[u8], ciphertext: &[u8]) {
+    ghash.update_padded(aad);
+    ghash.update_padded(ciphertext);
+
+    let aad_bits = aad.len() as u128 * 8;
+    let ciphertext_bits = ciphertext.len() as u128 * 8;
+    ghash.update_u128((aad_bits << 64) | ciphertext_bits);
+}
+
+/// XOR `mask` into `block`.
+fn xor(block: &mut Block, mask: &Block) {
+    for (a, b) in block.iter_mut().zip(mask.iter()) {
+        *a ^= *b;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Gmac {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.mask.as_mut_slice().zeroize();
+    }
+}
+
+opaque_debug::implement!(Gmac);
@@ -29,14 +29,35 @@
 )]
 #![warn(missing_docs, rust_2018_idioms)]
 
-pub use polyval::universal_hash;
+pub use polyval::{
+    universal_hash, AuthKey, BlockSizeUser, KeyInit, KeySizeUser, Reset, TagHex, UniversalHash,
+    UniversalHashExt,
+};
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub use polyval::DynUniversalHash;
+
+#[cfg(feature = "zeroize")]
+pub use polyval::zeroize_key;
+
+#[cfg(feature = "secrecy")]
+pub use polyval::secrecy;
+
+#[cfg(feature = "hex")]
+pub use polyval::base16ct;
+
+#[cfg(feature = "diagnostics")]
+pub use polyval::diagnostics;
+
+mod gmac;
+
+pub use crate::gmac::{gmac, Gmac};
 
 use polyval::Polyval;
-use universal_hash::{
-    consts::U16,
-    crypto_common::{BlockSizeUser, KeySizeUser, ParBlocksSizeUser},
-    KeyInit, UhfBackend, UhfClosure, UniversalHash,
-};
+use universal_hash::{consts::U16, crypto_common::ParBlocksSizeUser, UhfBackend, UhfClosure};
 
 #[cfg(feature = "zeroize")]
 use zeroize::Zeroize;
@@ -55,14 +76,21 @@ pub type Tag = universal_hash::Block<GHash>;
 /// GHASH is a universal hash function used for message authentication in
 /// the AES-GCM authenticated encryption cipher.
 #[derive(Clone)]
-pub struct GHash(Polyval);
+pub struct GHash(
+    Polyval,
+    /// Blocks input tail-first by [`GHash::update_reverse_order`]
+    #[cfg(feature = "hazmat")]
+    Option<ReverseSuffix>,
+);
 
 impl KeySizeUser for GHash {
     type KeySize = U16;
 }
 
 impl GHash {
-    /// Initialize GHASH with the given `H` field element and initial block
+    /// Initialize GHASH with the given `H` field element and initial block,
+    /// which is encoded as big endian (i.e. `finalize` returns
+    /// `init_block.to_be_bytes()` if no data is input)
     #[inline]
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
         let mut h = *h;
@@ -75,13 +103,503 @@ impl GHash {
         h.zeroize();
 
         #[allow(clippy::let_and_return)]
-        let result = GHash(Polyval::new_with_init_block(&h_polyval, init_block));
+        let result = Self::from_polyval(Polyval::new_with_init_block(&h_polyval, init_block));
 
         #[cfg(feature = "zeroize")]
         h_polyval.zeroize();
 
         result
     }
+
+    /// Initialize GHASH with the given [`AuthKey`].
+    #[inline]
+    pub fn new_from_authkey(key: &AuthKey) -> Self {
+        Self::new(key.as_key())
+    }
+
+    /// Initialize GHASH with the given key as a byte array.
+    #[inline]
+    pub fn new_from_array(h: &[u8; 16]) -> Self {
+        Self::new(h.into())
+    }
+
+    /// Initialize GHASH with the given secret key.
+    #[cfg(feature = "secrecy")]
+    #[inline]
+    pub fn new_from_secret(key: &secrecy::SecretBox<Key>) -> Self {
+        use secrecy::ExposeSecret;
+        Self::new(key.expose_secret())
+    }
+
+    /// Initialize GHASH with a key given as a hex string (upper or lower
+    /// case), e.g. one embedded in firmware as a string literal.
+    #[cfg(feature = "hex")]
+    pub fn from_hex_key(s: &str) -> Result<Self, base16ct::Error> {
+        let mut key = Key::default();
+        let result = match base16ct::mixed::decode(s, &mut key).map(|decoded| decoded.len()) {
+            Ok(len) if len == key.len() => Ok(Self::new(&key)),
+            Ok(_) => Err(base16ct::Error::InvalidLength),
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+            key.as_mut_slice().zeroize();
+        }
+
+        result
+    }
+
+    /// Input the given blocks, reporting how they were dispatched to the
+    /// backend's block processing methods.
+    #[cfg(feature = "diagnostics")]
+    pub fn update_counted(&mut self, blocks: &[Block]) -> diagnostics::ProcStats {
+        let start = self.0.proc_stats();
+        self.update(blocks);
+        self.0.proc_stats().since(start)
+    }
+
+    /// Input a single block given as a `u128`, encoded as big endian.
+    ///
+    /// This matches the encoding GCM uses for its lengths block, i.e.
+    /// `(len(A) << 64) | len(C)` with lengths in bits.
+    #[inline]
+    pub fn update_u128(&mut self, block: u128) {
+        self.update_block(&block.to_be_bytes().into());
+    }
+
+    /// Input data (zero-padding any trailing partial block, like
+    /// [`UniversalHash::update_padded`]) while reporting progress.
+    ///
+    /// The data is processed in chunks of 64 KiB, and after each chunk
+    /// `cb` is invoked with the total number of bytes processed so far. The
+    /// last invocation is always with `data.len()`, including when `data` is
+    /// empty.
+    pub fn update_with_progress(&mut self, data: &[u8], mut cb: impl FnMut(usize)) {
+        /// Number of blocks processed between progress callbacks (64 KiB)
+        const PROGRESS_CHUNK_BLOCKS: usize = 4096;
+
+        let (blocks, tail) = Block::slice_as_chunks(data);
+        let mut processed = 0;
+
+        for chunk in blocks.chunks(PROGRESS_CHUNK_BLOCKS) {
+            self.update(chunk);
+            processed += chunk.len() * 16;
+            cb(processed);
+        }
+
+        if !tail.is_empty() || data.is_empty() {
+            self.update_padded(tail);
+            cb(data.len());
+        }
+    }
+
+    /// Input a single block.
+    ///
+    /// Equivalent to `update(&[*block])`, but passes the block directly to
+    /// the backend.
+    #[inline]
+    pub fn update_block(&mut self, block: &Block) {
+        let mut block = *block;
+        block.reverse();
+        self.0.update_block(&block);
+    }
+
+    /// Input blocks supplied last-to-first, i.e. `blocks[0]` is the final
+    /// block of the message, `blocks[1]` the one before it, and so on.
+    ///
+    /// Successive calls continue towards the start of the message, so the
+    /// tag is the same as absorbing the blocks input by
+    /// [`UniversalHash::update`] (and the other forward methods) followed by
+    /// the blocks input by this method in reverse order. For example,
+    /// `update(&[a, b])` followed by `update_reverse_order(&[e, d])` and
+    /// `update_reverse_order(&[c])` is equivalent to `update(&[a, b, c, d, e])`.
+    ///
+    /// The blocks are kept apart from the forward accumulator, weighted by
+    /// the power of `H` for their distance from the end of the message, and
+    /// folded into it at finalization. [`GHash::as_polyval`] therefore
+    /// doesn't reflect them, while [`GHash::into_polyval`] and serialization
+    /// fold them in, so further input is appended after them.
+    #[cfg(feature = "hazmat")]
+    pub fn update_reverse_order(&mut self, blocks: &[Block]) {
+        if blocks.is_empty() {
+            return;
+        }
+
+        let suffix = self.1.get_or_insert_with(|| ReverseSuffix::new(&self.0));
+
+        for block in blocks {
+            let mut block = *block;
+            block.reverse();
+            suffix.update_block(&block);
+        }
+    }
+
+    /// Input blocks given as byte arrays, without converting each one to a
+    /// [`Block`].
+    pub fn update_raw_blocks(&mut self, blocks: &[[u8; 16]]) {
+        self.update(Block::cast_slice_from_core(blocks));
+    }
+
+    /// Input blocks from an iterator, e.g. ones generated lazily, without
+    /// collecting them into a slice first.
+    ///
+    /// See [`Polyval::update_iter`].
+    pub fn update_iter(&mut self, blocks: impl Iterator<Item = Block>) {
+        self.0.update_iter(blocks.map(|mut block| {
+            block.reverse();
+            block
+        }));
+    }
+
+    /// Input `count` blocks located every `stride` bytes within `base`, i.e.
+    /// the block `i` is `base[i * stride..][..16]`, without copying them into
+    /// a contiguous buffer first.
+    ///
+    /// # Panics
+    ///
+    /// If `stride` is less than the block size or `base` is too short to
+    /// contain `count` blocks.
+    pub fn update_strided(&mut self, base: &[u8], stride: usize, count: usize) {
+        assert!(stride >= 16, "stride must be at least the block size");
+
+        for i in 0..count {
+            let block = <&Block>::try_from(&base[i * stride..][..16]).unwrap();
+            self.update_block(block);
+        }
+    }
+
+    /// Verify a buffer consisting of data followed by its 16-byte tag,
+    /// returning the authenticated data on success.
+    ///
+    /// The data is zero-padded (as with [`UniversalHash::update_padded`])
+    /// and the tag is compared in constant time. Buffers shorter than a tag
+    /// are rejected.
+    pub fn verify_streamed(mut self, data_then_tag: &[u8]) -> Result<&[u8], universal_hash::Error> {
+        let split = data_then_tag
+            .len()
+            .checked_sub(16)
+            .ok_or(universal_hash::Error)?;
+        let (data, tag) = data_then_tag.split_at(split);
+
+        self.update_padded(data);
+        self.verify(&Tag::try_from(tag).map_err(|_| universal_hash::Error)?)?;
+        Ok(data)
+    }
+
+    /// Verify a batch of independent messages against their tags under this
+    /// instance's key, returning the indices of any which fail.
+    ///
+    /// Each message is zero-padded (as with [`UniversalHash::update_padded`])
+    /// and hashed starting from the initial block, i.e. any input already
+    /// processed by `self` is ignored. Tags are compared in constant time.
+    #[cfg(feature = "alloc")]
+    pub fn verify_batch(&self, items: &[(&[u8], &Tag)]) -> Result<(), alloc::vec::Vec<usize>> {
+        let failures: alloc::vec::Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (data, tag))| {
+                let mut ghash = self.clone_reset();
+                ghash.update_padded(data);
+                ghash.verify(tag).err().map(|_| i)
+            })
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Input data, padding any trailing partial block with `pad_byte`
+    /// followed by zeroes.
+    ///
+    /// If `set_length_bit` is `true`, a padding block is appended even when
+    /// the data is a multiple of the block size, which makes the padding
+    /// unambiguous, e.g. `update_padded_with(data, 0x80, true)` implements
+    /// `10*` padding.
+    ///
+    /// `update_padded_with(data, 0, false)` is equivalent to
+    /// [`UniversalHash::update_padded`]. Any other combination of arguments
+    /// is **not** compatible with GHASH as used by AES-GCM.
+    pub fn update_padded_with(&mut self, data: &[u8], pad_byte: u8, set_length_bit: bool) {
+        let (blocks, tail) = Block::slice_as_chunks(data);
+        self.update(blocks);
+
+        if !tail.is_empty() || set_length_bit {
+            let mut padded = Block::default();
+            padded[..tail.len()].copy_from_slice(tail);
+            padded[tail.len()] = pad_byte;
+            self.update_block(&padded);
+        }
+    }
+
+    /// Input a final partial block, zero-extended to the block size, then get
+    /// GHASH output.
+    ///
+    /// This makes explicit that only the last block of a message may be
+    /// partial: as it consumes `self`, nothing can be input afterwards. The
+    /// result is the same as calling [`UniversalHash::update_padded`] with
+    /// `data` and then finalizing.
+    ///
+    /// # Panics
+    ///
+    /// If `data` is not shorter than the block size.
+    #[must_use]
+    pub fn finalize_partial(mut self, data: &[u8]) -> Tag {
+        assert!(
+            data.len() < 16,
+            "final partial block must be shorter than a block"
+        );
+        self.update_padded(data);
+        self.finalize()
+    }
+
+    /// Input data (zero-padding any trailing partial block, like
+    /// [`UniversalHash::update_padded`]), returning the updated state.
+    ///
+    /// This allows the tag to be computed in a single expression:
+    ///
+    /// ```
+    /// use ghash::{universal_hash::{KeyInit, UniversalHash}, GHash};
+    ///
+    /// let h = [0x42; 16];
+    /// let tag = GHash::new(&h.into()).absorb_padded(b"hello world").finalize();
+    ///
+    /// let mut ghash = GHash::new(&h.into());
+    /// ghash.update_padded(b"hello world");
+    /// assert_eq!(tag, ghash.finalize());
+    /// ```
+    pub fn absorb_padded(mut self, data: &[u8]) -> Self {
+        self.update_padded(data);
+        self
+    }
+
+    /// Clone this instance with the same key, discarding all input processed
+    /// so far, i.e. the clone starts again from the initial block.
+    #[inline]
+    pub fn clone_reset(&self) -> Self {
+        Self::from_polyval(self.0.clone_reset())
+    }
+
+    /// Overwrite the accumulator with the given GHASH output, e.g. one
+    /// obtained by finalizing a clone, so hashing can resume from it.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// Setting the accumulator to arbitrary values can be used to forge tags.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[inline]
+    pub fn set_accumulator(&mut self, acc: &Block) {
+        let mut acc = *acc;
+        acc.reverse();
+        self.0.set_accumulator(&acc);
+        self.1 = None;
+    }
+
+    /// Get GHASH output along with the accumulator bytes, which can be
+    /// restored into an instance with the same key using
+    /// [`GHash::set_accumulator`].
+    ///
+    /// The accumulator is in GHASH's byte order, i.e. it's the tag itself.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// The accumulator of an unfinished computation reveals information
+    /// about the key, so it must be kept confidential.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[inline]
+    #[must_use]
+    pub fn finalize_with_state(self) -> (Tag, [u8; 16]) {
+        let tag = self.finalize();
+        (tag, tag.into())
+    }
+
+    /// Get GHASH output XORed with the given mask, e.g. the encrypted
+    /// pre-counter block `E_K(J0)` as used by GCM.
+    ///
+    /// The mask is applied after the output is converted to GHASH's byte
+    /// order.
+    #[inline]
+    #[must_use]
+    pub fn finalize_xor(self, mask: &Block) -> Tag {
+        let mut tag = self.finalize();
+        for (a, b) in tag.iter_mut().zip(mask.iter()) {
+            *a ^= b;
+        }
+        tag
+    }
+
+    /// Get GHASH output over exactly the input so far.
+    ///
+    /// This is the same as [`UniversalHash::finalize`]: neither appends a
+    /// block encoding the input lengths, so constructions which require one
+    /// must input it themselves, or use [`GHash::finalize_gcm`]:
+    ///
+    /// ```
+    /// use ghash::{universal_hash::{KeyInit, UniversalHash}, GHash};
+    ///
+    /// let (aad, ct) = (b"header", b"ciphertext");
+    ///
+    /// let mut ghash = GHash::new(&[0x42; 16].into());
+    /// ghash.update_padded(aad);
+    /// ghash.update_padded(ct);
+    /// let tag = ghash.clone().finalize_gcm(aad.len(), ct.len());
+    ///
+    /// // Input the GCM lengths block manually
+    /// ghash.update_u128(((aad.len() as u128 * 8) << 64) | (ct.len() as u128 * 8));
+    /// assert_eq!(tag, ghash.finalize_raw());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn finalize_raw(self) -> Tag {
+        UniversalHash::finalize(self)
+    }
+
+    /// Input the GCM lengths block for additional data and ciphertext of the
+    /// given lengths in bytes, then get GHASH output.
+    ///
+    /// The block contains the lengths in bits as big endian 64-bit integers,
+    /// as described in [NIST SP 800-38D] Section 7.1. The result is `S`,
+    /// which GCM encrypts with `E_K(J0)` to produce the tag (see
+    /// [`GHash::finalize_xor`]).
+    ///
+    /// [NIST SP 800-38D]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+    #[must_use]
+    pub fn finalize_gcm(mut self, aad_len: usize, ct_len: usize) -> Tag {
+        let aad_bits = aad_len as u128 * 8;
+        let ct_bits = ct_len as u128 * 8;
+        self.update_u128((aad_bits << 64) | ct_bits);
+        self.finalize_raw()
+    }
+
+    /// Get GHASH output wrapped so that it's zeroized when dropped.
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    #[must_use]
+    pub fn finalize_zeroizing(self) -> zeroize::Zeroizing<Tag> {
+        zeroize::Zeroizing::new(self.finalize())
+    }
+
+    /// Write the leading `min(out.len(), 16)` bytes of GHASH output to `out`,
+    /// i.e. a truncated tag.
+    ///
+    /// # ⚠️ Warning: truncation reduces forgery resistance
+    ///
+    /// A tag truncated to `t` bits can be forged by guessing with probability
+    /// at least `2^-t` per attempt. Worse, with GHASH-style MACs each
+    /// successful forgery reveals information about the key which makes
+    /// subsequent forgeries easier (see [NIST SP 800-38D] Appendix C), so
+    /// short tags require strict limits on the number of failed verifications
+    /// and the message length.
+    ///
+    /// [NIST SP 800-38D]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+    pub fn finalize_truncated(self, out: &mut [u8]) {
+        let tag = self.finalize();
+        let len = out.len().min(tag.len());
+        out[..len].copy_from_slice(&tag[..len]);
+    }
+
+    /// Get GHASH output as a byte array.
+    #[inline]
+    #[must_use]
+    pub fn finalize_bytes(self) -> [u8; 16] {
+        self.finalize().into()
+    }
+
+    /// Get the GHASH tag for an empty input under the given key without
+    /// constructing an instance.
+    ///
+    /// This is always the all-zero block, as the accumulator starts at zero
+    /// (which is unchanged by the byte reversal relative to POLYVAL).
+    #[inline]
+    #[must_use]
+    pub fn empty_tag(_h: &Key) -> Tag {
+        Tag::default()
+    }
+
+    /// Reset to the given initial block (encoded as big endian, as with
+    /// [`GHash::new_with_init_block`]), which subsequent calls to
+    /// [`Reset::reset`] will also restore, keeping `H`.
+    #[inline]
+    pub fn reset_with_init_block(&mut self, init_block: u128) {
+        self.0.reset_with_init_block(init_block);
+
+        #[cfg(feature = "hazmat")]
+        {
+            self.1 = None;
+        }
+    }
+
+    /// Get the number of bytes absorbed so far, including any padding,
+    /// saturating at [`u64::MAX`] (see [`Polyval::padded_len`]).
+    #[inline]
+    pub fn padded_len(&self) -> u64 {
+        self.block_count().saturating_mul(16)
+    }
+
+    /// Has nothing been absorbed since this instance was initialized or reset?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.block_count() == 0
+    }
+
+    /// Get the number of blocks processed, saturating at [`u64::MAX`]
+    #[inline]
+    pub fn block_count(&self) -> u64 {
+        #[cfg(feature = "hazmat")]
+        if let Some(suffix) = &self.1 {
+            return self.0.block_count().saturating_add(suffix.block_count);
+        }
+
+        self.0.block_count()
+    }
+
+    /// Wrap an existing [`Polyval`] instance as GHASH.
+    ///
+    /// This bypasses the conversion of the GHASH key `H` into a POLYVAL key,
+    /// so `polyval` must have been initialized with an already-converted key,
+    /// e.g. one obtained from [`GHash::into_polyval`].
+    #[inline]
+    pub fn from_polyval(polyval: Polyval) -> Self {
+        GHash(
+            polyval,
+            #[cfg(feature = "hazmat")]
+            None,
+        )
+    }
+
+    /// Borrow the inner [`Polyval`] instance.
+    ///
+    /// The inner POLYVAL instance operates on byte-reversed blocks relative
+    /// to GHASH: each input block and the output tag are reversed.
+    #[inline]
+    pub fn as_polyval(&self) -> &Polyval {
+        &self.0
+    }
+
+    /// Unwrap the inner [`Polyval`] instance.
+    ///
+    /// The inner POLYVAL instance operates on byte-reversed blocks relative
+    /// to GHASH: each input block and the output tag are reversed.
+    #[inline]
+    pub fn into_polyval(self) -> Polyval {
+        #[cfg(feature = "hazmat")]
+        if let Some(suffix) = &self.1 {
+            let mut polyval = self.0;
+            let acc = suffix.fold(&polyval.clone().finalize());
+            polyval.set_accumulator(&acc);
+            return polyval;
+        }
+
+        self.0
+    }
 }
 
 impl KeyInit for GHash {
@@ -135,9 +653,130 @@ impl UniversalHash for GHash {
     #[inline]
     fn finalize(self) -> Tag {
         let mut output = self.0.finalize();
+
+        #[cfg(feature = "hazmat")]
+        if let Some(suffix) = &self.1 {
+            output = suffix.fold(&output);
+        }
+
         output.reverse();
         output
     }
 }
 
+/// # ⚠️ Warning: contains the key!
+///
+/// The serialized form includes the raw (POLYVAL-domain) `H` key bytes, so it
+/// must be protected (e.g. encrypted at rest) in the same way as the key
+/// itself. See the `polyval` crate for the format.
+#[cfg(feature = "serde")]
+impl serde::Serialize for GHash {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[cfg(feature = "hazmat")]
+        if self.1.is_some() {
+            return self.clone().into_polyval().serialize(serializer);
+        }
+
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GHash {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Polyval::deserialize(deserializer).map(GHash::from_polyval)
+    }
+}
+
+impl Reset for GHash {
+    /// Reset to the initial block, keeping `H`
+    #[inline]
+    fn reset(&mut self) {
+        self.0.reset();
+
+        #[cfg(feature = "hazmat")]
+        {
+            self.1 = None;
+        }
+    }
+}
+
+/// Blocks input tail-first by [`GHash::update_reverse_order`], in the POLYVAL
+/// domain.
+///
+/// After `k` blocks `X_1..X_k` (in the order they were input), `sum` is
+/// `X_1 * H + X_2 * H^2 + ... + X_k * H^k`. Folding it into a forward
+/// accumulator `Y` gives `Y * H^k + sum`, i.e. the Horner evaluation of the
+/// forward blocks followed by `X_k..X_1`.
+#[cfg(feature = "hazmat")]
+#[derive(Clone)]
+struct ReverseSuffix {
+    /// POLYVAL key `H`
+    h: Block,
+
+    /// `H^k`
+    power: Block,
+
+    /// Sum of each block multiplied by the power of `H` for its position
+    sum: Block,
+
+    /// Number of blocks input, i.e. `k`
+    block_count: u64,
+}
+
+#[cfg(feature = "hazmat")]
+impl ReverseSuffix {
+    /// Multiplicative identity for POLYVAL's `dot(a, b) = a * b * x^-128`,
+    /// i.e. `x^128` reduced modulo POLYVAL's polynomial
+    const ONE: u128 = 0xc200_0000_0000_0000_0000_0000_0000_0001;
+
+    /// Start a suffix for the key of the given POLYVAL instance.
+    fn new(polyval: &Polyval) -> Self {
+        use polyval::hazmat::block_from_u128_le;
+
+        // The key isn't stored separately from the POLYVAL instance, so
+        // recover it by hashing the identity from a zero accumulator
+        let mut polyval = polyval.clone();
+        polyval.set_accumulator(&Block::default());
+        polyval.update_block(&block_from_u128_le(Self::ONE));
+
+        Self {
+            h: polyval.finalize(),
+            power: block_from_u128_le(Self::ONE),
+            sum: Block::default(),
+            block_count: 0,
+        }
+    }
+
+    /// Input the block preceding all of the blocks input so far.
+    fn update_block(&mut self, block: &Block) {
+        use polyval::hazmat::gf_mul;
+
+        self.power = gf_mul(&self.power, &self.h);
+        self.sum = xor(&self.sum, &gf_mul(block, &self.power));
+        self.block_count = self.block_count.saturating_add(1);
+    }
+
+    /// Fold into the given forward accumulator.
+    fn fold(&self, acc: &Block) -> Block {
+        xor(&polyval::hazmat::gf_mul(acc, &self.power), &self.sum)
+    }
+}
+
+#[cfg(all(feature = "hazmat", feature = "zeroize"))]
+impl Drop for ReverseSuffix {
+    fn drop(&mut self) {
+        self.h.zeroize();
+        self.power.zeroize();
+        self.sum.zeroize();
+    }
+}
+
+/// XOR two POLYVAL field elements, i.e. add them.
+#[cfg(feature = "hazmat")]
+fn xor(a: &Block, b: &Block) -> Block {
+    use polyval::hazmat::{block_from_u128_le, block_to_u128_le};
+    block_from_u128_le(block_to_u128_le(a) ^ block_to_u128_le(b))
+}
+
 opaque_debug::implement!(GHash);
@@ -1,7 +1,4 @@
-use ghash::{
-    universal_hash::{KeyInit, UniversalHash},
-    GHash,
-};
+use ghash::{AuthKey, BlockSizeUser, GHash, Gmac, KeyInit, KeySizeUser, Reset, UniversalHash};
 use hex_literal::hex;
 
 //
@@ -24,3 +21,692 @@ fn ghash_test_vector() {
     let result = ghash.finalize();
     assert_eq!(&GHASH_RESULT[..], result.as_slice());
 }
+
+#[test]
+fn inner_polyval() {
+    let ghash = GHash::new(&H.into());
+
+    let mut polyval = ghash.clone().into_polyval();
+    for x in [X_1, X_2] {
+        let mut block = x;
+        block.reverse();
+        polyval.update(&[block.into()]);
+    }
+
+    let mut result = polyval.finalize();
+    result.reverse();
+    assert_eq!(&GHASH_RESULT[..], result.as_slice());
+
+    let mut ghash = GHash::from_polyval(ghash.as_polyval().clone());
+    ghash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&GHASH_RESULT[..], ghash.finalize().as_slice());
+}
+
+#[test]
+fn auth_key() {
+    let key = AuthKey::from(H);
+    assert_eq!(format!("{:?}", key), "AuthKey(***)");
+
+    let mut ghash = GHash::new_from_authkey(&key);
+    ghash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&GHASH_RESULT[..], ghash.finalize().as_slice());
+}
+
+/// GMAC test vector from the NIST CAVP `gcmEncryptExtIV128.rsp`
+/// (`PTlen = 0`, `AADlen = 128`, `Count = 0`).
+#[test]
+fn gmac_test_vector() {
+    let h = hex!("7cb681cd037b6d137a95f4db99c48351");
+    let encrypted_j0 = hex!("bbbb748469cfceb4914079e259dd9e9c");
+    let aad = hex!("7a43ec1d9c0a5a78a0b16533a6213cab");
+    let tag = hex!("209fcc8d3675ed938e9c7166709dd946");
+
+    let gmac = Gmac::from_subkey(&h.into(), &encrypted_j0.into());
+    assert_eq!(&tag[..], gmac.clone().compute(&aad).as_slice());
+    assert!(gmac.clone().verify(&aad, &tag.into()).is_ok());
+    assert!(gmac.verify(&aad[1..], &tag.into()).is_err());
+}
+
+#[test]
+fn gmac_from_cipher() {
+    let nonce = hex!("e0e00f19fed7ba0136a797f3");
+    let aad = hex!("7a43ec1d9c0a5a78a0b16533a6213cab");
+    let tag = hex!("209fcc8d3675ed938e9c7166709dd946");
+
+    // Stand-in for AES-128 under key `77be63708971c4e240d1cb79e8d77feb` which
+    // only supports the two blocks GMAC encrypts.
+    let encrypt_block = |block: &mut ghash::Block| {
+        let output = if block.as_slice() == [0u8; 16] {
+            hex!("7cb681cd037b6d137a95f4db99c48351")
+        } else if block.as_slice() == hex!("e0e00f19fed7ba0136a797f300000001") {
+            hex!("bbbb748469cfceb4914079e259dd9e9c")
+        } else {
+            panic!("unexpected block");
+        };
+        block.copy_from_slice(&output);
+    };
+
+    let gmac = Gmac::new_from_cipher(encrypt_block, &nonce);
+    assert_eq!(&tag[..], gmac.compute(&aad).as_slice());
+}
+
+/// AES-GCM test cases from "The Galois/Counter Mode of Operation (GCM)"
+/// by McGrew and Viega, which are also used by NIST.
+#[test]
+fn gcm_test_vectors() {
+    // Test cases 1 and 2: K = 0^128, IV = 0^96
+    let h = hex!("66e94bd4ef8a2c3b884cfa59ca342b2e");
+    let encrypted_j0 = hex!("58e2fccefa7e3061367f1d57a4e7455a");
+    let ct = hex!("0388dace60b6a392f328c2b971b2fe78");
+
+    assert_eq!(
+        ghash::gmac(&h.into(), &encrypted_j0.into(), &[], &[]).as_slice(),
+        encrypted_j0
+    );
+    assert_eq!(
+        ghash::gmac(&h.into(), &encrypted_j0.into(), &[], &ct).as_slice(),
+        hex!("ab6e47d42cec13bdf53a67b21257bddf")
+    );
+
+    // Test cases 3 and 4: K = feffe9928665731c6d6a8f9467308308,
+    // IV = cafebabefacedbaddecaf888
+    let h = hex!("b83b533708bf535d0aa6e52980d53b78");
+    let aad = hex!("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+    let ct = hex!(
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e"
+        "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985"
+    );
+
+    // Recover `E_K(J0)` from test case 3, which has no additional data
+    let mut encrypted_j0 = ghash::gmac(&h.into(), &Default::default(), &[], &ct);
+    for (a, b) in encrypted_j0
+        .iter_mut()
+        .zip(hex!("4d5c2af327cd64a62cf35abd2ba6fab4"))
+    {
+        *a ^= b;
+    }
+
+    assert_eq!(
+        ghash::gmac(&h.into(), &encrypted_j0, &aad, &ct[..60]).as_slice(),
+        hex!("5bc94fbc3221a5db94fae95ae7121a47")
+    );
+
+    // AAD-only: NIST GMAC test vector also checked by `gmac_test_vector`
+    assert_eq!(
+        ghash::gmac(
+            &hex!("7cb681cd037b6d137a95f4db99c48351").into(),
+            &hex!("bbbb748469cfceb4914079e259dd9e9c").into(),
+            &hex!("7a43ec1d9c0a5a78a0b16533a6213cab"),
+            &[]
+        )
+        .as_slice(),
+        hex!("209fcc8d3675ed938e9c7166709dd946")
+    );
+}
+
+#[test]
+fn clone_reset() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_2.into(), X_1.into()]);
+
+    let mut fresh = ghash.clone_reset();
+    assert_eq!(fresh.block_count(), 0);
+
+    fresh.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&GHASH_RESULT[..], fresh.finalize().as_slice());
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn set_accumulator() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into()]);
+    let checkpoint = ghash.clone().finalize();
+
+    let mut resumed = GHash::new(&H.into());
+    resumed.set_accumulator(&checkpoint);
+    resumed.update(&[X_2.into()]);
+    assert_eq!(&GHASH_RESULT[..], resumed.finalize().as_slice());
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn finalize_with_state() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into(), X_2.into()]);
+    let (tag, state) = ghash.finalize_with_state();
+    assert_eq!(&GHASH_RESULT[..], tag.as_slice());
+
+    let mut fresh = GHash::new(&H.into());
+    fresh.set_accumulator(&state.into());
+    assert_eq!(fresh.clone().finalize(), tag);
+
+    // Hashing resumes from the restored state
+    let mut resumed = GHash::new(&H.into());
+    resumed.update(&[X_1.into()]);
+    let (_, state) = resumed.finalize_with_state();
+
+    let mut fresh = GHash::new(&H.into());
+    fresh.set_accumulator(&state.into());
+    fresh.update(&[X_2.into()]);
+    assert_eq!(fresh.finalize(), tag);
+}
+
+#[test]
+fn finalize_xor() {
+    let mut hash = GHash::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+
+    let mask = X_2.into();
+    let mut expected = hash.clone().finalize();
+    for (a, b) in expected.iter_mut().zip(X_2.iter()) {
+        *a ^= b;
+    }
+    assert_eq!(hash.finalize_xor(&mask), expected);
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn update_counted() {
+    let blocks = [X_1.into(); 9];
+    let mut hash = GHash::new(&H.into());
+    let stats = hash.update_counted(&blocks);
+
+    // All backends process one block at a time
+    assert_eq!(stats.par_blocks_size, 1);
+    assert_eq!((stats.par_calls, stats.single_calls), (0, 9));
+
+    let mut expected = GHash::new(&H.into());
+    expected.update(&blocks);
+    assert_eq!(hash.finalize(), expected.finalize());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+    let mut hash = GHash::new_with_init_block(&H.into(), init_block);
+    hash.update(&[X_1.into()]);
+
+    let json = serde_json::to_string(&hash).unwrap();
+    let mut restored: GHash = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.block_count(), 1);
+
+    hash.update(&[X_2.into()]);
+    restored.update(&[X_2.into()]);
+    assert_eq!(hash.clone().finalize(), restored.clone().finalize());
+
+    // The initial block is also restored
+    hash.reset();
+    restored.reset();
+    assert_eq!(hash.finalize(), restored.finalize());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn finalize_zeroizing() {
+    use core::mem::ManuallyDrop;
+
+    let mut hash = GHash::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+
+    let mut tag = ManuallyDrop::new(hash.finalize_zeroizing());
+    assert_eq!(&GHASH_RESULT[..], tag.as_slice());
+
+    let ptr = &mut tag as *mut ManuallyDrop<_>;
+    unsafe {
+        ManuallyDrop::drop(&mut *ptr);
+        assert_eq!(*(ptr as *const [u8; 16]), [0u8; 16]);
+    }
+}
+
+#[test]
+fn update_raw_blocks() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update_raw_blocks(&[X_1, X_2]);
+    assert_eq!(&GHASH_RESULT[..], ghash.finalize().as_slice());
+}
+
+#[test]
+fn update_iter() {
+    let blocks: Vec<ghash::Block> = (0..=255u8)
+        .cycle()
+        .take(16 * 19)
+        .collect::<Vec<_>>()
+        .chunks(16)
+        .map(|chunk| <[u8; 16]>::try_from(chunk).unwrap().into())
+        .collect();
+
+    for n in [0, 1, 8, 9, 19] {
+        let mut iter = GHash::new(&H.into());
+        iter.update_iter(blocks[..n].iter().copied());
+
+        let mut slice = GHash::new(&H.into());
+        slice.update(&blocks[..n]);
+
+        assert_eq!(iter.finalize(), slice.finalize());
+    }
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn update_reverse_order() {
+    // xorshift64 for deterministic pseudorandom input
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..64 {
+        let n = (next() % 40) as usize;
+        let blocks: Vec<ghash::Block> = (0..n)
+            .map(|_| {
+                let mut block = ghash::Block::default();
+                for byte in block.iter_mut() {
+                    *byte = next() as u8;
+                }
+                block
+            })
+            .collect();
+
+        let mut forward = GHash::new_with_init_block(&H.into(), next().into());
+        let mut backward = forward.clone();
+        forward.update(&blocks);
+
+        // Input a forward prefix, then the rest tail-first over several calls
+        let (prefix, suffix) = blocks.split_at((next() % (n as u64 + 1)) as usize);
+        backward.update(prefix);
+
+        let mut remaining = suffix;
+        while !remaining.is_empty() {
+            let len = 1 + (next() % remaining.len() as u64) as usize;
+            let (rest, chunk) = remaining.split_at(remaining.len() - len);
+            let reversed: Vec<_> = chunk.iter().rev().copied().collect();
+            backward.update_reverse_order(&reversed);
+            remaining = rest;
+        }
+
+        assert_eq!(backward.block_count(), n as u64);
+        assert_eq!(forward.clone().finalize(), backward.clone().finalize());
+
+        let restored = GHash::from_polyval(backward.into_polyval());
+        assert_eq!(forward.finalize(), restored.finalize());
+    }
+}
+
+#[test]
+fn update_strided() {
+    // Blocks interleaved with 5 bytes of metadata each
+    let stride = 21;
+    let base: Vec<u8> = (0..=255).cycle().take(stride * 9).collect();
+
+    for count in [0, 1, 9] {
+        let mut strided = GHash::new(&H.into());
+        strided.update_strided(&base, stride, count);
+
+        let blocks: Vec<_> = (0..count)
+            .map(|i| {
+                <[u8; 16]>::try_from(&base[i * stride..][..16])
+                    .unwrap()
+                    .into()
+            })
+            .collect();
+        let mut contiguous = GHash::new(&H.into());
+        contiguous.update(&blocks);
+
+        assert_eq!(strided.finalize(), contiguous.finalize());
+    }
+}
+
+#[test]
+fn tag_hex() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(
+        format!("{}", ghash::TagHex(&ghash.finalize())),
+        "bd9b3997046731fb96251b91f9c99d7a"
+    );
+}
+
+#[test]
+fn empty_tag() {
+    assert_eq!(
+        GHash::empty_tag(&H.into()),
+        GHash::new(&H.into()).finalize()
+    );
+}
+
+#[test]
+fn finalize_bytes() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(ghash.clone().finalize_bytes(), ghash.finalize().as_slice());
+}
+
+#[test]
+fn init_block() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+    let ghash = GHash::new_with_init_block(&H.into(), init_block);
+    assert_eq!(ghash.finalize().as_slice(), init_block.to_be_bytes());
+}
+
+#[test]
+fn reset_with_init_block() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    let mut hash = GHash::new(&H.into());
+    hash.update(&[X_1.into()]);
+    hash.reset_with_init_block(init_block);
+    hash.update(&[X_2.into()]);
+
+    let mut expected = GHash::new_with_init_block(&H.into(), init_block);
+    expected.update(&[X_2.into()]);
+    assert_eq!(hash.clone().finalize(), expected.clone().finalize());
+
+    // Subsequent resets restore the new initial block
+    hash.reset();
+    expected.reset();
+    assert_eq!(hash.finalize(), expected.finalize());
+}
+
+#[test]
+fn update_block() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update_block(&X_1.into());
+    ghash.update_block(&X_2.into());
+    assert_eq!(&GHASH_RESULT[..], ghash.finalize().as_slice());
+}
+
+#[test]
+fn update_u128() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update_u128(u128::from_be_bytes(X_1));
+    ghash.update_u128(u128::from_be_bytes(X_2));
+    assert_eq!(&GHASH_RESULT[..], ghash.finalize().as_slice());
+}
+
+#[test]
+fn finalize_partial() {
+    for len in [0, 1, 15] {
+        let mut expected = GHash::new(&H.into());
+        expected.update(&[X_1.into()]);
+        expected.update_padded(&X_2[..len]);
+
+        let mut hash = GHash::new(&H.into());
+        hash.update(&[X_1.into()]);
+        assert_eq!(hash.finalize_partial(&X_2[..len]), expected.finalize());
+    }
+}
+
+#[test]
+#[should_panic]
+fn finalize_partial_full_block() {
+    let _ = GHash::new(&H.into()).finalize_partial(&X_1);
+}
+
+#[test]
+fn new_from_array() {
+    let mut from_array = GHash::new_from_array(&H);
+    from_array.update(&[X_1.into(), X_2.into()]);
+
+    let mut from_key = GHash::new(&H.into());
+    from_key.update(&[X_1.into(), X_2.into()]);
+
+    assert_eq!(&GHASH_RESULT[..], from_array.finalize().as_slice());
+    assert_eq!(&GHASH_RESULT[..], from_key.finalize().as_slice());
+}
+
+#[test]
+fn finalize_truncated() {
+    let mut hash = GHash::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+    let tag = hash.clone().finalize();
+
+    for len in [0, 8, 12, 16, 20] {
+        let mut out = [0xff; 20];
+        hash.clone().finalize_truncated(&mut out[..len]);
+
+        let n = len.min(16);
+        assert_eq!(out[..n], tag[..n]);
+        assert!(out[n..].iter().all(|&b| b == 0xff));
+    }
+}
+
+#[test]
+fn absorb_padded() {
+    let data = [X_1, X_2].concat();
+
+    for len in [0, 1, 16, 17, 32] {
+        let mut expected = GHash::new(&H.into());
+        expected.update_padded(&data[..len]);
+        expected.update_padded(&data[..len]);
+
+        let tag = GHash::new(&H.into())
+            .absorb_padded(&data[..len])
+            .absorb_padded(&data[..len])
+            .finalize();
+
+        assert_eq!(tag, expected.finalize());
+    }
+}
+
+/// `Polyval::finalize_with_order` with `ByteOrder::Ghash` computes
+/// GHASH when given the converted key and byte-reversed inputs.
+#[test]
+fn polyval_finalize_with_ghash_order() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into(), X_2.into()]);
+
+    let mut h = H;
+    h.reverse();
+
+    let mut polyval = polyval::Polyval::new(&polyval::mulx(&h.into()));
+    polyval.update_reversed(&[X_1.into(), X_2.into()]);
+
+    assert_eq!(
+        ghash.finalize(),
+        polyval.finalize_with_order(polyval::ByteOrder::Ghash)
+    );
+}
+
+#[test]
+fn update_padded_with() {
+    let data = [X_1, X_2].concat();
+
+    for len in [0, 1, 16, 17, 32] {
+        let mut expected = GHash::new(&H.into());
+        expected.update_padded(&data[..len]);
+
+        let mut ghash = GHash::new(&H.into());
+        ghash.update_padded_with(&data[..len], 0, false);
+        assert_eq!(ghash.finalize(), expected.finalize());
+    }
+
+    let mut padded = [0u8; 48];
+    padded[..32].copy_from_slice(&data);
+    padded[32] = 0x80;
+
+    let mut expected = GHash::new(&H.into());
+    expected.update_padded(&padded);
+
+    let mut ghash = GHash::new(&H.into());
+    ghash.update_padded_with(&data, 0x80, true);
+    assert_eq!(ghash.finalize(), expected.finalize());
+}
+
+#[test]
+fn block_count() {
+    let mut ghash = GHash::new(&H.into());
+    ghash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(ghash.block_count(), 2);
+}
+
+#[test]
+fn padded_len() {
+    let mut ghash = GHash::new(&H.into());
+    assert!(ghash.is_empty());
+    assert_eq!(ghash.padded_len(), 0);
+
+    ghash.update_padded(&[0u8; 17]);
+    assert!(!ghash.is_empty());
+    assert_eq!(ghash.padded_len(), 32);
+}
+
+/// Generic code can use the traits re-exported at the crate root as bounds.
+#[test]
+fn root_reexports() {
+    fn sizes<T: BlockSizeUser + KeySizeUser>() -> (usize, usize) {
+        (T::block_size(), T::key_size())
+    }
+
+    assert_eq!(sizes::<GHash>(), (16, 16));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_universal_hash() {
+    let mut hashes: Vec<Box<dyn ghash::DynUniversalHash>> = vec![
+        Box::new(GHash::new(&H.into())),
+        Box::new(polyval::Polyval::new(&H.into())),
+    ];
+
+    for hash in &mut hashes {
+        hash.dyn_update(&[X_1.into(), X_2.into()]);
+    }
+
+    let tags: Vec<_> = hashes.into_iter().map(|h| h.finalize_boxed()).collect();
+    assert_eq!(&GHASH_RESULT[..], tags[0].as_slice());
+    assert_eq!(
+        &hex!("f7a3b47b846119fae5b7866cf5e5b77e")[..],
+        tags[1].as_slice()
+    );
+}
+
+#[test]
+fn universal_hash_ext() {
+    use ghash::{universal_hash::consts::U16, UniversalHashExt};
+
+    fn check<U>(expected: &[u8])
+    where
+        U: UniversalHashExt + BlockSizeUser<BlockSize = U16> + KeySizeUser<KeySize = U16>,
+    {
+        let key = H.into();
+        let blocks = [X_1.into(), X_2.into()];
+        let chained = U::new(&key).chain_update(&blocks).finalize();
+        assert_eq!(chained.as_slice(), expected);
+
+        let data = [X_1, X_2].concat();
+        assert_eq!(U::compute(&key, &data).as_slice(), expected);
+        assert!(U::new(&key).chain_update(&blocks).verify(&chained).is_ok());
+    }
+
+    check::<GHash>(&GHASH_RESULT);
+    check::<polyval::Polyval>(&hex!("f7a3b47b846119fae5b7866cf5e5b77e"));
+}
+
+#[test]
+fn verify_streamed() {
+    let buf = [X_1, X_2, GHASH_RESULT].concat();
+    let hash = GHash::new(&H.into());
+    assert_eq!(hash.clone().verify_streamed(&buf), Ok(&buf[..32]));
+
+    let mut tampered = buf.clone();
+    tampered[0] ^= 1;
+    assert!(hash.clone().verify_streamed(&tampered).is_err());
+
+    let mut bad_tag = buf.clone();
+    bad_tag[47] ^= 1;
+    assert!(hash.clone().verify_streamed(&bad_tag).is_err());
+
+    assert!(hash.verify_streamed(&buf[..15]).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn verify_batch() {
+    let hash = GHash::new(&H.into());
+    let msg_a = [X_1, X_2].concat();
+    let msg_b = b"short message";
+    let mut hasher = hash.clone_reset();
+    hasher.update(&[X_1.into(), X_2.into()]);
+    let tag_a = hasher.finalize();
+
+    let mut hasher = hash.clone_reset();
+    hasher.update_padded(msg_b);
+    let tag_b = hasher.finalize();
+    let mut tampered = msg_a.clone();
+    tampered[3] ^= 1;
+
+    let items: [(&[u8], _); 4] = [
+        (&msg_a, &tag_a),
+        (&tampered, &tag_a),
+        (msg_b, &tag_b),
+        (msg_b, &tag_a),
+    ];
+    assert_eq!(hash.verify_batch(&items), Err(vec![1, 3]));
+    assert_eq!(hash.verify_batch(&[items[0], items[2]]), Ok(()));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_key() {
+    let mut key = ghash::Key::from(H);
+    ghash::zeroize_key(&mut key);
+    assert_eq!(key.as_slice(), [0u8; 16]);
+}
+
+#[cfg(feature = "hex")]
+#[test]
+fn from_hex_key() {
+    use ghash::base16ct::Error;
+
+    for s in [
+        "25629347589242761d31f826ba4b757b",
+        "25629347589242761D31F826BA4B757B",
+    ] {
+        let mut hash = GHash::from_hex_key(s).unwrap();
+        hash.update(&[X_1.into(), X_2.into()]);
+        assert_eq!(&GHASH_RESULT[..], hash.finalize().as_slice());
+    }
+
+    assert!(matches!(
+        GHash::from_hex_key("25629347589242761d31f826ba4b757g"),
+        Err(Error::InvalidEncoding)
+    ));
+    assert!(matches!(
+        GHash::from_hex_key("25629347589242761d31f826ba4b75"),
+        Err(Error::InvalidLength)
+    ));
+    assert!(matches!(
+        GHash::from_hex_key("25629347589242761d31f826ba4b757b00"),
+        Err(Error::InvalidLength)
+    ));
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn new_from_secret() {
+    use ghash::secrecy::SecretBox;
+
+    let key = SecretBox::new(Box::new(ghash::Key::from(H)));
+    let mut hash = GHash::new_from_secret(&key);
+    hash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&GHASH_RESULT[..], hash.finalize().as_slice());
+}
+
+#[test]
+fn update_with_progress() {
+    let data = vec![0x42u8; 3 * 65536 + 5];
+    let mut progress = Vec::new();
+
+    let mut hash = GHash::new(&H.into());
+    hash.update_with_progress(&data, |n| progress.push(n));
+    assert_eq!(progress, [65536, 2 * 65536, 3 * 65536, data.len()]);
+
+    let mut expected = GHash::new(&H.into());
+    expected.update_padded(&data);
+    assert_eq!(hash.finalize(), expected.finalize());
+}
@@ -0,0 +1,28 @@
+//! Poly1305 as used by ChaCha20Poly1305.
+
+use crate::{Key, Poly1305, Tag};
+use universal_hash::{KeyInit, UniversalHash};
+
+/// Compute the Poly1305 tag over the given additional data and ciphertext as
+/// described in [RFC 8439 Section 2.8], using the given one-time key.
+///
+/// The input to Poly1305 is the additional data and ciphertext, each
+/// zero-padded to a multiple of 16 bytes, followed by a block containing
+/// their lengths in bytes as little endian 64-bit integers.
+///
+/// The one-time key is the first 32 bytes of the ChaCha20 keystream block
+/// with a counter of 0.
+///
+/// [RFC 8439 Section 2.8]: https://tools.ietf.org/html/rfc8439#section-2.8
+#[must_use]
+pub fn aead_tag(key: &Key, aad: &[u8], ct: &[u8]) -> Tag {
+    let mut poly1305 = Poly1305::new(key);
+    poly1305.update_padded(aad);
+    poly1305.update_padded(ct);
+
+    let mut lengths = crate::Block::default();
+    lengths[..8].copy_from_slice(&(aad.len() as u64).to_le_bytes());
+    lengths[8..].copy_from_slice(&(ct.len() as u64).to_le_bytes());
+    poly1305.update(&[lengths]);
+    poly1305.finalize()
+}
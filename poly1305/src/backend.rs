@@ -2,14 +2,21 @@
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft)
+    not(any(poly1305_force_soft, miri))
 ))]
 pub(crate) mod avx2;
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft)
+    not(any(poly1305_force_soft, miri))
 ))]
 pub(crate) mod autodetect;
 
+#[cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    not(any(poly1305_force_soft, miri))
+))]
+pub(crate) mod wasm;
+
 pub(crate) mod soft;
@@ -6,8 +6,16 @@ use universal_hash::{consts::U16, crypto_common::BlockSizeUser, UhfClosure, Univ
 use crate::{backend, Block, Key, Tag};
 use core::mem::ManuallyDrop;
 
+// Besides CPUID, `cpufeatures` (v0.2.8+) checks XCR0 to ensure the OS has
+// enabled saving the AVX (`ymm`) register state, so AVX2 is only selected
+// when it's actually usable.
 cpufeatures::new!(avx2_cpuid, "avx2");
 
+/// Is AVX2 available?
+pub(crate) fn has_avx2() -> bool {
+    avx2_cpuid::get()
+}
+
 pub struct State {
     inner: Inner,
     token: avx2_cpuid::InitToken,
@@ -50,6 +58,16 @@ impl State {
             unsafe { (*self.inner.soft).compute_block(block, partial) }
         }
     }
+
+    /// Get the number of calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn proc_stats(&self) -> crate::diagnostics::ProcStats {
+        if self.token.get() {
+            unsafe { self.inner.avx2.proc_stats() }
+        } else {
+            unsafe { self.inner.soft.proc_stats() }
+        }
+    }
 }
 
 impl UniversalHash for State {
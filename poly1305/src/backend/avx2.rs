@@ -24,6 +24,9 @@ use universal_hash::{
 
 use crate::{Block, Key, Tag};
 
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::ProcStats;
+
 mod helpers;
 use self::helpers::*;
 
@@ -46,6 +49,8 @@ pub(crate) struct State {
     cached_blocks: [Block; 4],
     num_cached_blocks: usize,
     partial_block: Option<Block>,
+    #[cfg(feature = "diagnostics")]
+    stats: ProcStats,
 }
 
 impl State {
@@ -65,9 +70,17 @@ impl State {
             cached_blocks: [Block::default(); 4],
             num_cached_blocks: 0,
             partial_block: None,
+            #[cfg(feature = "diagnostics")]
+            stats: ProcStats::new(4),
         }
     }
 
+    /// Get the number of calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
     /// Process four Poly1305 blocks at once.
     #[target_feature(enable = "avx2")]
     pub(crate) unsafe fn compute_par_blocks(&mut self, blocks: &ParBlocks) {
@@ -182,10 +195,24 @@ impl ParBlocksSizeUser for State {
 
 impl UhfBackend for State {
     fn proc_block(&mut self, block: &Block) {
+        debug_assert_avx2();
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
+
         unsafe { self.compute_block(block, false) };
     }
 
     fn proc_par_blocks(&mut self, blocks: &ParBlocks) {
+        debug_assert_avx2();
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.par_calls += 1;
+        }
+
         if self.num_cached_blocks == 0 {
             // Fast path.
             unsafe { self.compute_par_blocks(blocks) };
@@ -207,3 +234,13 @@ impl UhfBackend for State {
         }
     }
 }
+
+/// Catch this backend being selected on a CPU without AVX2, which would
+/// otherwise crash with SIGILL.
+#[inline(always)]
+fn debug_assert_avx2() {
+    debug_assert!(
+        super::autodetect::has_avx2(),
+        "AVX2 backend used without CPU support"
+    );
+}
@@ -20,11 +20,16 @@ use universal_hash::{
 
 use crate::{Block, Key, Tag};
 
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::ProcStats;
+
 #[derive(Clone, Default)]
 pub(crate) struct State {
-    r: [u32; 5],
-    h: [u32; 5],
+    pub(super) r: [u32; 5],
+    pub(super) h: [u32; 5],
     pad: [u32; 4],
+    #[cfg(feature = "diagnostics")]
+    pub(super) stats: ProcStats,
 }
 
 impl State {
@@ -44,9 +49,20 @@ impl State {
         poly.pad[2] = u32::from_le_bytes(key[24..28].try_into().unwrap());
         poly.pad[3] = u32::from_le_bytes(key[28..32].try_into().unwrap());
 
+        #[cfg(feature = "diagnostics")]
+        {
+            poly.stats = ProcStats::new(1);
+        }
+
         poly
     }
 
+    /// Get the number of calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
     /// Compute a Poly1305 block
     pub(crate) fn compute_block(&mut self, block: &Block, partial: bool) {
         let hibit = if partial { 0 } else { 1 << 24 };
@@ -239,6 +255,8 @@ impl Drop for State {
         self.r.zeroize();
         self.h.zeroize();
         self.pad.zeroize();
+        #[cfg(feature = "diagnostics")]
+        self.stats.zeroize();
     }
 }
 
@@ -252,6 +270,11 @@ impl ParBlocksSizeUser for State {
 
 impl UhfBackend for State {
     fn proc_block(&mut self, block: &Block) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
+
         self.compute_block(block, false);
     }
 }
@@ -0,0 +1,312 @@
+//! WASM SIMD implementation of the Poly1305 state machine.
+//!
+//! Processes two blocks at a time by computing:
+//!
+//! ```text
+//! h = (h + m_0) * r^2 + m_1 * r
+//! ```
+//!
+//! ...where each of the two multiplications is performed in one lane of a
+//! `u64x2` vector, using the same 26-bit limb representation as the "soft"
+//! backend. Single and partial blocks are handled by the "soft" backend.
+
+use core::arch::wasm32::*;
+
+use universal_hash::{
+    consts::{U16, U2},
+    crypto_common::{BlockSizeUser, ParBlocksSizeUser},
+    ParBlocks, UhfBackend, UhfClosure, UniversalHash,
+};
+
+use crate::{backend::soft, Block, Key, Tag};
+
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::ProcStats;
+
+#[derive(Clone)]
+pub(crate) struct State {
+    soft: soft::State,
+    r2: [u32; 5],
+}
+
+impl State {
+    /// Initialize Poly1305 [`State`] with the given key
+    pub(crate) fn new(key: &Key) -> State {
+        #[allow(unused_mut)]
+        let mut soft = soft::State::new(key);
+
+        // Calls are counted in the "soft" state
+        #[cfg(feature = "diagnostics")]
+        {
+            soft.stats = ProcStats::new(2);
+        }
+
+        // Compute r^2 by multiplying r (as a message block without the high
+        // bit set) by r, then fully carry it so each limb is 26-bits.
+        let mut r_block = Block::default();
+        for (i, chunk) in r_block.chunks_mut(4).enumerate() {
+            let r = &soft.r;
+            let word = match i {
+                0 => r[0] | (r[1] << 26),
+                1 => (r[1] >> 6) | (r[2] << 20),
+                2 => (r[2] >> 12) | (r[3] << 14),
+                _ => (r[3] >> 18) | (r[4] << 8),
+            };
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        let mut square = soft::State::new(key);
+        square.compute_block(&r_block, true);
+
+        let mut r2 = square.h;
+        let c = r2[1] >> 26;
+        r2[1] &= 0x3ff_ffff;
+        r2[2] += c;
+
+        State { soft, r2 }
+    }
+
+    /// Get the number of calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn proc_stats(&self) -> ProcStats {
+        self.soft.proc_stats()
+    }
+
+    /// Compute a Poly1305 block
+    pub(crate) fn compute_block(&mut self, block: &Block, partial: bool) {
+        self.soft.compute_block(block, partial);
+    }
+
+    /// Compute two Poly1305 blocks
+    fn compute_par_blocks(&mut self, blocks: &ParBlocks<Self>) {
+        let r = &self.soft.r;
+        let r2 = &self.r2;
+        let h = &self.soft.h;
+        let m0 = limbs(&blocks[0]);
+        let m1 = limbs(&blocks[1]);
+
+        // Lane 0: (h + m_0) * r^2, lane 1: m_1 * r
+        let x = [
+            u64x2(u64::from(h[0] + m0[0]), u64::from(m1[0])),
+            u64x2(u64::from(h[1] + m0[1]), u64::from(m1[1])),
+            u64x2(u64::from(h[2] + m0[2]), u64::from(m1[2])),
+            u64x2(u64::from(h[3] + m0[3]), u64::from(m1[3])),
+            u64x2(u64::from(h[4] + m0[4]), u64::from(m1[4])),
+        ];
+
+        let r = [
+            u64x2(u64::from(r2[0]), u64::from(r[0])),
+            u64x2(u64::from(r2[1]), u64::from(r[1])),
+            u64x2(u64::from(r2[2]), u64::from(r[2])),
+            u64x2(u64::from(r2[3]), u64::from(r[3])),
+            u64x2(u64::from(r2[4]), u64::from(r[4])),
+        ];
+
+        let s1 = u64x2_mul(r[1], u64x2_splat(5));
+        let s2 = u64x2_mul(r[2], u64x2_splat(5));
+        let s3 = u64x2_mul(r[3], u64x2_splat(5));
+        let s4 = u64x2_mul(r[4], u64x2_splat(5));
+
+        let d0 = sum5(
+            u64x2_mul(x[0], r[0]),
+            u64x2_mul(x[1], s4),
+            u64x2_mul(x[2], s3),
+            u64x2_mul(x[3], s2),
+            u64x2_mul(x[4], s1),
+        );
+
+        let d1 = sum5(
+            u64x2_mul(x[0], r[1]),
+            u64x2_mul(x[1], r[0]),
+            u64x2_mul(x[2], s4),
+            u64x2_mul(x[3], s3),
+            u64x2_mul(x[4], s2),
+        );
+
+        let d2 = sum5(
+            u64x2_mul(x[0], r[2]),
+            u64x2_mul(x[1], r[1]),
+            u64x2_mul(x[2], r[0]),
+            u64x2_mul(x[3], s4),
+            u64x2_mul(x[4], s3),
+        );
+
+        let d3 = sum5(
+            u64x2_mul(x[0], r[3]),
+            u64x2_mul(x[1], r[2]),
+            u64x2_mul(x[2], r[1]),
+            u64x2_mul(x[3], r[0]),
+            u64x2_mul(x[4], s4),
+        );
+
+        let d4 = sum5(
+            u64x2_mul(x[0], r[4]),
+            u64x2_mul(x[1], r[3]),
+            u64x2_mul(x[2], r[2]),
+            u64x2_mul(x[3], r[1]),
+            u64x2_mul(x[4], r[0]),
+        );
+
+        // Sum the lanes. The sums may exceed 2^32 after carrying, so the
+        // (partial) h %= p reduction is performed in 64-bit arithmetic.
+        let d0 = hsum(d0);
+        let mut d1 = hsum(d1);
+        let mut d2 = hsum(d2);
+        let mut d3 = hsum(d3);
+        let mut d4 = hsum(d4);
+
+        let mut h0 = d0 & 0x3ff_ffff;
+        d1 += d0 >> 26;
+        let mut h1 = d1 & 0x3ff_ffff;
+        d2 += d1 >> 26;
+        let h2 = d2 & 0x3ff_ffff;
+        d3 += d2 >> 26;
+        let h3 = d3 & 0x3ff_ffff;
+        d4 += d3 >> 26;
+        let h4 = d4 & 0x3ff_ffff;
+        h0 += (d4 >> 26) * 5;
+        h1 += h0 >> 26;
+        h0 &= 0x3ff_ffff;
+
+        self.soft.h = [h0 as u32, h1 as u32, h2 as u32, h3 as u32, h4 as u32];
+    }
+
+    /// Finalize output producing a [`Tag`]
+    pub(crate) fn finalize_mut(&mut self) -> Tag {
+        self.soft.finalize_mut()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for State {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.r2.zeroize();
+    }
+}
+
+impl BlockSizeUser for State {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for State {
+    type ParBlocksSize = U2;
+}
+
+impl UhfBackend for State {
+    fn proc_block(&mut self, block: &Block) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.soft.stats.single_calls += 1;
+        }
+
+        self.compute_block(block, false);
+    }
+
+    fn proc_par_blocks(&mut self, blocks: &ParBlocks<Self>) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.soft.stats.par_calls += 1;
+        }
+
+        self.compute_par_blocks(blocks);
+    }
+}
+
+impl UniversalHash for State {
+    fn update_with_backend(&mut self, f: impl UhfClosure<BlockSize = Self::BlockSize>) {
+        f.call(self);
+    }
+
+    /// Finalize output producing a [`Tag`]
+    fn finalize(mut self) -> Tag {
+        self.finalize_mut()
+    }
+}
+
+/// Split a full message block (with the high bit set) into 26-bit limbs
+#[inline(always)]
+fn limbs(block: &Block) -> [u32; 5] {
+    [
+        (u32::from_le_bytes(block[0..4].try_into().unwrap())) & 0x3ff_ffff,
+        (u32::from_le_bytes(block[3..7].try_into().unwrap()) >> 2) & 0x3ff_ffff,
+        (u32::from_le_bytes(block[6..10].try_into().unwrap()) >> 4) & 0x3ff_ffff,
+        (u32::from_le_bytes(block[9..13].try_into().unwrap()) >> 6) & 0x3ff_ffff,
+        (u32::from_le_bytes(block[12..16].try_into().unwrap()) >> 8) | (1 << 24),
+    ]
+}
+
+#[inline(always)]
+fn sum5(a: v128, b: v128, c: v128, d: v128, e: v128) -> v128 {
+    u64x2_add(u64x2_add(u64x2_add(a, b), u64x2_add(c, d)), e)
+}
+
+#[inline(always)]
+fn hsum(v: v128) -> u64 {
+    u64x2_extract_lane::<0>(v) + u64x2_extract_lane::<1>(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+    use crate::{backend::soft, Block};
+    use universal_hash::{ParBlocks, UhfBackend, UniversalHash};
+
+    /// Compare the WASM SIMD backend against the "soft" backend, using the
+    /// committed AVX2 fuzzer crash cases as inputs.
+    fn compare_with_soft(data: &[u8]) {
+        let key = data[0..32].try_into().unwrap();
+        let mut wasm = State::new(key);
+        let mut soft = soft::State::new(key);
+
+        let (blocks, remaining) = Block::slice_as_chunks(&data[32..]);
+        let (pairs, tail) = ParBlocks::<State>::slice_as_chunks(blocks);
+
+        for (i, pair) in pairs.iter().enumerate() {
+            wasm.proc_par_blocks(pair);
+            soft.proc_block(&pair[0]);
+            soft.proc_block(&pair[1]);
+
+            assert_eq!((i, wasm.clone().finalize()), (i, soft.clone().finalize()));
+        }
+
+        for block in tail {
+            wasm.proc_block(block);
+            soft.proc_block(block);
+        }
+
+        if !remaining.is_empty() {
+            let mut block = Block::default();
+            block[..remaining.len()].copy_from_slice(remaining);
+            block[remaining.len()] = 1;
+            wasm.compute_block(&block, true);
+            soft.compute_block(&block, true);
+        }
+
+        assert_eq!(wasm.finalize(), soft.finalize());
+    }
+
+    #[test]
+    fn fuzz_crash_cases() {
+        for data in [
+            &include_bytes!("../fuzz/id=000000,sig=06,src=000014,op=flip4,pos=11")[..],
+            include_bytes!("../fuzz/id=000001,sig=06,src=000006+000014,op=splice,rep=64"),
+            include_bytes!("../fuzz/id=000002,sig=06,src=000008+000014,op=splice,rep=32"),
+            include_bytes!("../fuzz/id=000003,sig=06,src=000003,op=havoc,rep=64"),
+            include_bytes!("../fuzz/id=000004,sig=06,src=000022+000005,op=splice,rep=32"),
+            include_bytes!("../fuzz/id=000005,sig=06,src=000008+000007,op=splice,rep=128"),
+            include_bytes!("../fuzz/id=000006,sig=06,src=000005,op=havoc,rep=8"),
+            include_bytes!("../fuzz/id=000007,sig=06,src=000024+000000,op=splice,rep=64"),
+            include_bytes!(
+                "../fuzz/id=000008,sig=06,src=000019,time=165655+000011,op=splice,rep=128"
+            ),
+        ] {
+            compare_with_soft(data);
+        }
+    }
+
+    #[test]
+    fn all_ones() {
+        compare_with_soft(&[0xff; 32 + 16 * 64 + 5]);
+    }
+}
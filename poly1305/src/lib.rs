@@ -48,22 +48,42 @@
 )]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "std")]
 extern crate std;
 
-pub use universal_hash;
+#[cfg(feature = "secrecy")]
+pub use secrecy;
+
+#[cfg(feature = "hex")]
+pub use base16ct;
+
+pub use universal_hash::{
+    self,
+    crypto_common::{BlockSizeUser, KeySizeUser},
+    KeyInit, Reset, UniversalHash,
+};
 
 use universal_hash::{
     consts::{U16, U32},
-    crypto_common::{BlockSizeUser, KeySizeUser},
-    KeyInit, UhfClosure, UniversalHash,
+    UhfClosure,
 };
 
+mod aead;
+mod auth_key;
 mod backend;
+mod once;
+
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+
+pub use crate::{aead::aead_tag, auth_key::AuthKey, once::Poly1305Once};
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft),
+    not(any(poly1305_force_soft, miri)),
     target_feature = "avx2", // Fuzz tests bypass AVX2 autodetection code
     any(fuzzing, test)
 ))]
@@ -71,13 +91,27 @@ mod fuzz;
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft)
+    not(any(poly1305_force_soft, miri))
 ))]
 use crate::backend::autodetect::State;
 
-#[cfg(not(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft)
+#[cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    not(any(poly1305_force_soft, miri))
+))]
+use crate::backend::wasm::State;
+
+#[cfg(not(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(any(poly1305_force_soft, miri))
+    ),
+    all(
+        target_arch = "wasm32",
+        target_feature = "simd128",
+        not(any(poly1305_force_soft, miri))
+    )
 )))]
 use crate::backend::soft::State;
 
@@ -96,6 +130,13 @@ pub type Block = universal_hash::Block<Poly1305>;
 /// Poly1305 tags (16-bytes)
 pub type Tag = universal_hash::Block<Poly1305>;
 
+/// Zeroize the given Poly1305 [`Key`] in place.
+#[cfg(feature = "zeroize")]
+pub fn zeroize_key(key: &mut Key) {
+    use zeroize::Zeroize;
+    key.as_mut_slice().zeroize();
+}
+
 /// The Poly1305 universal hash function.
 ///
 /// Note that Poly1305 is not a traditional MAC and is single-use only
@@ -136,9 +177,59 @@ impl UniversalHash for Poly1305 {
 }
 
 impl Poly1305 {
+    /// Initialize Poly1305 with the given [`AuthKey`].
+    pub fn new_from_authkey(key: &AuthKey) -> Self {
+        Self::new(key.as_key())
+    }
+
+    /// Initialize Poly1305 with the given key as a byte array.
+    pub fn new_from_array(key: &[u8; KEY_SIZE]) -> Self {
+        Self::new(key.into())
+    }
+
+    /// Initialize Poly1305 with the given secret key.
+    #[cfg(feature = "secrecy")]
+    pub fn new_from_secret(key: &secrecy::SecretBox<Key>) -> Self {
+        use secrecy::ExposeSecret;
+        Self::new(key.expose_secret())
+    }
+
+    /// Initialize Poly1305 with a key given as a hex string (upper or lower
+    /// case), e.g. one embedded in firmware as a string literal.
+    #[cfg(feature = "hex")]
+    pub fn from_hex_key(s: &str) -> Result<Self, base16ct::Error> {
+        let mut key = Key::default();
+        let result = match base16ct::mixed::decode(s, &mut key).map(|decoded| decoded.len()) {
+            Ok(len) if len == key.len() => Ok(Self::new(&key)),
+            Ok(_) => Err(base16ct::Error::InvalidLength),
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+            key.as_mut_slice().zeroize();
+        }
+
+        result
+    }
+
+    /// Get the Poly1305 tag for an empty input under the given key without
+    /// constructing an instance.
+    ///
+    /// As the accumulator starts at zero, this is the second half of the key
+    /// (i.e. `s`).
+    #[must_use]
+    pub fn empty_tag(key: &Key) -> Tag {
+        let mut tag = Tag::default();
+        tag.copy_from_slice(&key[16..]);
+        tag
+    }
+
     /// Compute unpadded Poly1305 for the given input data.
     ///
     /// The main use case for this is XSalsa20Poly1305.
+    #[must_use]
     pub fn compute_unpadded(mut self, data: &[u8]) -> Tag {
         let (blocks, remaining) = Block::slice_as_chunks(data);
 
@@ -155,13 +246,95 @@ impl Poly1305 {
 
         self.state.finalize()
     }
+
+    /// Verify the unpadded Poly1305 tag for the given input data in constant
+    /// time.
+    ///
+    /// See [`Poly1305::compute_unpadded`].
+    pub fn verify_unpadded(self, data: &[u8], expected: &Tag) -> Result<(), universal_hash::Error> {
+        use subtle::ConstantTimeEq;
+
+        if self.compute_unpadded(data).ct_eq(expected).into() {
+            Ok(())
+        } else {
+            Err(universal_hash::Error)
+        }
+    }
+
+    /// Compute unpadded Poly1305 for the given input data, returning the tag
+    /// as a byte array.
+    ///
+    /// See [`Poly1305::compute_unpadded`].
+    #[must_use]
+    pub fn compute_unpadded_bytes(self, data: &[u8]) -> [u8; BLOCK_SIZE] {
+        self.compute_unpadded(data).into()
+    }
+
+    /// Compute unpadded Poly1305 tags for a batch of independent messages,
+    /// each with its own one-time key.
+    ///
+    /// See [`Poly1305::compute_unpadded`].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn compute_batch(items: &[(&Key, &[u8])]) -> alloc::vec::Vec<Tag> {
+        items
+            .iter()
+            .map(|(key, data)| Self::new(key).compute_unpadded(data))
+            .collect()
+    }
+
+    /// Get the hashed output as a byte array.
+    #[must_use]
+    pub fn finalize_bytes(self) -> [u8; BLOCK_SIZE] {
+        self.finalize().into()
+    }
+
+    /// Input the given blocks, reporting how they were dispatched to the
+    /// backend's block processing methods.
+    #[cfg(feature = "diagnostics")]
+    pub fn update_counted(&mut self, blocks: &[Block]) -> diagnostics::ProcStats {
+        let start = self.state.proc_stats();
+        self.update(blocks);
+        self.state.proc_stats().since(start)
+    }
+
+    /// Process exactly four blocks.
+    ///
+    /// On backends which process multiple blocks in parallel (e.g. AVX2) the
+    /// blocks are passed to the parallel code path, while the "soft" backend
+    /// processes them one at a time.
+    pub fn update_par_blocks(&mut self, blocks: &[Block; 4]) {
+        self.update(blocks);
+    }
+
+    /// Process the given blocks, returning the updated state.
+    ///
+    /// This allows builder-style usage, e.g.
+    /// `Poly1305::new(key).chain(blocks).finalize()`.
+    pub fn chain(mut self, blocks: &[Block]) -> Self {
+        self.update(blocks);
+        self
+    }
+
+    /// Process the given data, zero-padding any trailing partial block,
+    /// returning the updated state.
+    ///
+    /// Note that the zero-padding is absorbed as part of the message: if more
+    /// data is input afterwards, the result will differ from hashing the
+    /// concatenated inputs. This is only meaningful for constructions which
+    /// explicitly specify padding of each input segment (e.g. the RFC 8439
+    /// AEAD construction), and is otherwise a misuse.
+    pub fn chain_padded(mut self, data: &[u8]) -> Self {
+        self.update_padded(data);
+        self
+    }
 }
 
 opaque_debug::implement!(Poly1305);
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    not(poly1305_force_soft),
+    not(any(poly1305_force_soft, miri)),
     target_feature = "avx2", // Fuzz tests bypass AVX2 autodetection code
     any(fuzzing, test)
 ))]
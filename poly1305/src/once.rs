@@ -0,0 +1,52 @@
+//! Single-use Poly1305 wrapper.
+
+use crate::{AuthKey, Poly1305, Tag};
+use universal_hash::{
+    consts::U16, crypto_common::BlockSizeUser, KeyInit, UhfClosure, UniversalHash,
+};
+
+/// Poly1305 one-time authenticator which takes ownership of its key.
+///
+/// Unlike [`Poly1305`], which borrows its key, [`Poly1305Once::new`] consumes
+/// an [`AuthKey`], which can't be copied and (when the `zeroize` feature is
+/// enabled) is wiped as soon as it has been used for initialization, making
+/// it harder to accidentally reuse the key for another message. It also
+/// doesn't impl [`Clone`] or [`Reset`][universal_hash::Reset], so each
+/// instance can only ever produce a single tag.
+pub struct Poly1305Once {
+    inner: Poly1305,
+}
+
+impl Poly1305Once {
+    /// Initialize Poly1305 with the given key, consuming it.
+    pub fn new(key: AuthKey) -> Self {
+        Self {
+            inner: Poly1305::new(key.as_key()),
+        }
+    }
+
+    /// Compute unpadded Poly1305 for the given input data.
+    ///
+    /// See [`Poly1305::compute_unpadded`].
+    #[must_use]
+    pub fn compute_unpadded(self, data: &[u8]) -> Tag {
+        self.inner.compute_unpadded(data)
+    }
+}
+
+impl BlockSizeUser for Poly1305Once {
+    type BlockSize = U16;
+}
+
+impl UniversalHash for Poly1305Once {
+    fn update_with_backend(&mut self, f: impl UhfClosure<BlockSize = Self::BlockSize>) {
+        self.inner.update_with_backend(f);
+    }
+
+    /// Get the hashed output
+    fn finalize(self) -> Tag {
+        self.inner.finalize()
+    }
+}
+
+opaque_debug::implement!(Poly1305Once);
@@ -1,7 +1,7 @@
 use hex_literal::hex;
 use poly1305::{
-    universal_hash::{KeyInit, UniversalHash},
-    Block, Poly1305, KEY_SIZE,
+    AuthKey, Block, BlockSizeUser, KeyInit, KeySizeUser, Poly1305, Poly1305Once, UniversalHash,
+    KEY_SIZE,
 };
 use std::iter::repeat;
 
@@ -25,6 +25,16 @@ fn test_nacl_vector() {
 
     let result1 = Poly1305::new(key.as_ref()).compute_unpadded(&msg);
     assert_eq!(&expected[..], result1.as_slice());
+
+    assert!(Poly1305::new(key.as_ref())
+        .verify_unpadded(&msg, &expected.into())
+        .is_ok());
+
+    let mut tampered = expected;
+    tampered[15] ^= 1;
+    assert!(Poly1305::new(key.as_ref())
+        .verify_unpadded(&msg, &tampered.into())
+        .is_err());
 }
 
 #[test]
@@ -92,6 +102,35 @@ fn test_rfc7539_vector() {
     assert_eq!(&expected[..], result.as_slice());
 }
 
+/// ChaCha20-Poly1305 AEAD test vector from RFC 8439 Section 2.8.2
+#[test]
+fn aead_tag() {
+    let key = hex!("7bac2b252db447af09b67a55a4e955840ae1d6731075d9eb2a9375783ed553ff");
+    let aad = hex!("50515253c0c1c2c3c4c5c6c7");
+    let ct = hex!(
+        "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6"
+        "3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36"
+        "92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc"
+        "3ff4def08e4b7a9de576d26586cec64b6116"
+    );
+
+    assert_eq!(
+        poly1305::aead_tag(&key.into(), &aad, &ct).as_slice(),
+        hex!("1ae10b594f09e26a7e902ecbd0600691")
+    );
+}
+
+#[test]
+fn new_from_array() {
+    let key = [0x42; KEY_SIZE];
+    let msg = b"Cryptographic Forum Research Group";
+
+    assert_eq!(
+        Poly1305::new_from_array(&key).compute_unpadded(msg),
+        Poly1305::new(&key.into()).compute_unpadded(msg)
+    );
+}
+
 #[test]
 fn padded_input() {
     // poly1305 key and AAD from <https://tools.ietf.org/html/rfc8439#section-2.8.2>
@@ -103,3 +142,253 @@ fn padded_input() {
     poly.update_padded(&msg);
     assert_eq!(&expected[..], poly.finalize().as_slice());
 }
+
+#[test]
+fn chained_input() {
+    // poly1305 key and AAD from <https://tools.ietf.org/html/rfc8439#section-2.8.2>
+    let key = hex!("7bac2b252db447af09b67a55a4e955840ae1d6731075d9eb2a9375783ed553ff");
+    let msg = hex!("50515253c0c1c2c3c4c5c6c7");
+    let expected = hex!("ada56caa480fe6f5067039244a3d76ba");
+
+    let tag = Poly1305::new(key.as_ref()).chain_padded(&msg).finalize();
+    assert_eq!(&expected[..], tag.as_slice());
+
+    let mut block = Block::default();
+    block[..msg.len()].copy_from_slice(&msg);
+
+    let tag = Poly1305::new(key.as_ref()).chain(&[block]).finalize();
+    assert_eq!(&expected[..], tag.as_slice());
+}
+
+#[test]
+fn auth_key() {
+    // From <https://tools.ietf.org/html/rfc7539#section-2.5.2>
+    let key = AuthKey::from(hex!(
+        "85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b"
+    ));
+    let msg = hex!("43727970746f6772617068696320466f72756d2052657365617263682047726f7570");
+    let expected = hex!("a8061dc1305136c6c22b8baf0c0127a9");
+
+    assert_eq!(format!("{:?}", key), "AuthKey(***)");
+
+    let result = Poly1305::new_from_authkey(&key).compute_unpadded(&msg);
+    assert_eq!(&expected[..], result.as_slice());
+}
+
+#[test]
+fn empty_tag() {
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let expected = Poly1305::new(&key.into()).compute_unpadded(&[]);
+    assert_eq!(Poly1305::empty_tag(&key.into()), expected);
+}
+
+#[test]
+fn finalize_bytes() {
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let data = b"Cryptographic Forum Research Group";
+
+    let mut poly = Poly1305::new(&key.into());
+    poly.update_padded(data);
+    assert_eq!(poly.clone().finalize_bytes(), poly.finalize().as_slice());
+
+    let poly = Poly1305::new(&key.into());
+    assert_eq!(
+        poly.clone().compute_unpadded_bytes(data),
+        poly.compute_unpadded(data).as_slice()
+    );
+}
+
+#[test]
+fn clone_mid_computation() {
+    let key = [0x42; KEY_SIZE];
+    let blocks: Vec<Block> = (0u8..11).map(|i| Block::from([i; 16])).collect();
+
+    // Stop at an odd number of blocks so the AVX2 backend has buffered,
+    // partially processed state when cloned
+    let mut poly = Poly1305::new(&key.into());
+    poly.update(&blocks[..5]);
+
+    let mut clone = poly.clone();
+    poly.update(&blocks[5..]);
+    clone.update(&blocks[5..]);
+
+    let mut expected = Poly1305::new(&key.into());
+    expected.update(&blocks);
+
+    let expected = expected.finalize();
+    assert_eq!(poly.finalize(), expected);
+    assert_eq!(clone.finalize(), expected);
+}
+
+#[test]
+fn update_par_blocks() {
+    let key = [0x42; KEY_SIZE];
+    let blocks: Vec<Block> = (0u8..12).map(|i| Block::from([i; 16])).collect();
+
+    let mut expected = Poly1305::new(&key.into());
+    expected.update(&blocks);
+
+    let mut poly = Poly1305::new(&key.into());
+    for chunk in blocks.chunks_exact(4) {
+        poly.update_par_blocks(chunk.try_into().unwrap());
+    }
+
+    assert_eq!(poly.finalize(), expected.finalize());
+}
+
+#[test]
+fn poly1305_once() {
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let data = b"Cryptographic Forum Research Group";
+
+    let expected = Poly1305::new(&key.into()).compute_unpadded(data);
+    let result = Poly1305Once::new(key.into()).compute_unpadded(data);
+    assert_eq!(result, expected);
+
+    let mut mac = Poly1305Once::new(key.into());
+    mac.update_padded(data);
+
+    let mut expected = Poly1305::new(&key.into());
+    expected.update_padded(data);
+    assert_eq!(mac.finalize(), expected.finalize());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn poly1305_once_zeroized_on_drop() {
+    use core::mem::{size_of, ManuallyDrop};
+
+    const SIZE: usize = size_of::<Poly1305Once>();
+
+    let key = hex!("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b");
+    let contains_key = |bytes: &[u8; SIZE]| bytes.windows(4).any(|w| w == &key[16..20]);
+
+    let mut mac = ManuallyDrop::new(Poly1305Once::new(AuthKey::from(key)));
+    mac.update_padded(b"Cryptographic Forum Research Group");
+
+    let ptr: *mut ManuallyDrop<Poly1305Once> = &mut mac;
+    unsafe {
+        // The second half of the key is retained as is until the tag is computed
+        assert!(contains_key(&*(ptr as *const [u8; SIZE])));
+
+        ManuallyDrop::drop(&mut *ptr);
+        assert!(!contains_key(&*(ptr as *const [u8; SIZE])));
+        assert_eq!(*(ptr as *const [u8; SIZE]), [0u8; SIZE]);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn auth_key_zeroized_on_drop() {
+    use core::mem::ManuallyDrop;
+
+    let mut key = ManuallyDrop::new(AuthKey::from([0x42; KEY_SIZE]));
+    let ptr: *mut ManuallyDrop<AuthKey> = &mut key;
+    unsafe {
+        ManuallyDrop::drop(&mut *ptr);
+        assert_eq!(*(ptr as *const [u8; KEY_SIZE]), [0u8; KEY_SIZE]);
+    }
+}
+
+/// Generic code can use the traits re-exported at the crate root as bounds.
+#[test]
+fn root_reexports() {
+    fn sizes<T: BlockSizeUser + KeySizeUser>() -> (usize, usize) {
+        (T::block_size(), T::key_size())
+    }
+
+    assert_eq!(sizes::<Poly1305>(), (16, 32));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_key() {
+    let mut key = poly1305::Key::from([0x42; KEY_SIZE]);
+    poly1305::zeroize_key(&mut key);
+    assert_eq!(key.as_slice(), [0u8; KEY_SIZE]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn compute_batch() {
+    let keys: Vec<poly1305::Key> = (0..4u8)
+        .map(|i| [i.wrapping_mul(37); KEY_SIZE].into())
+        .collect();
+    let data: Vec<u8> = (0..=255).cycle().take(300).collect();
+    let items: Vec<_> = keys
+        .iter()
+        .zip([0, 1, 16, 300])
+        .map(|(key, len)| (key, &data[..len]))
+        .collect();
+
+    let tags = Poly1305::compute_batch(&items);
+    assert_eq!(tags.len(), items.len());
+
+    for ((key, data), tag) in items.iter().zip(&tags) {
+        assert_eq!(&Poly1305::new(key).compute_unpadded(data), tag);
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn update_counted() {
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let blocks: Vec<Block> = (0..9u8).map(|i| [i; 16].into()).collect();
+
+    let mut poly = Poly1305::new(&key.into());
+    let stats = poly.update_counted(&blocks);
+
+    let width = stats.par_blocks_size;
+    if width > 1 {
+        assert_eq!(
+            (stats.par_calls, stats.single_calls),
+            (9 / width, 9 % width)
+        );
+    } else {
+        assert_eq!((stats.par_calls, stats.single_calls), (0, 9));
+    }
+
+    let mut expected = Poly1305::new(&key.into());
+    expected.update(&blocks);
+    assert_eq!(poly.finalize(), expected.finalize());
+}
+
+#[cfg(feature = "hex")]
+#[test]
+fn from_hex_key() {
+    use poly1305::base16ct::Error;
+
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let data = b"Cryptographic Forum Research Group";
+    let expected = Poly1305::new(&key.into()).compute_unpadded(data);
+
+    let hash =
+        Poly1305::from_hex_key("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880")
+            .unwrap();
+    assert_eq!(hash.compute_unpadded(data), expected);
+
+    assert!(matches!(
+        Poly1305::from_hex_key("eea6a7251c1e72916d11c2cb214d3c25"),
+        Err(Error::InvalidLength)
+    ));
+    assert!(matches!(
+        Poly1305::from_hex_key("xea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880"),
+        Err(Error::InvalidEncoding)
+    ));
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn new_from_secret() {
+    use poly1305::secrecy::SecretBox;
+
+    let key = hex!("eea6a7251c1e72916d11c2cb214d3c252539121d8e234e652d651fa4c8cff880");
+    let data = b"Cryptographic Forum Research Group";
+
+    let secret = SecretBox::new(Box::new(poly1305::Key::from(key)));
+    let expected = Poly1305::new(&key.into()).compute_unpadded(data);
+    assert_eq!(
+        Poly1305::new_from_secret(&secret).compute_unpadded(data),
+        expected
+    );
+}
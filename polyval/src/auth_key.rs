@@ -0,0 +1,55 @@
+//! Key wrapper type.
+
+use crate::Key;
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// POLYVAL key wrapper which guards against accidentally leaking key material.
+///
+/// It deliberately doesn't impl [`Clone`], and its [`fmt::Debug`] impl
+/// doesn't reveal the key. When the `zeroize` feature is enabled, the key is
+/// zeroized when dropped.
+#[repr(transparent)]
+pub struct AuthKey(Key);
+
+impl AuthKey {
+    /// Wrap the given key.
+    pub fn new(key: Key) -> Self {
+        Self(key)
+    }
+
+    /// Borrow the wrapped key.
+    pub fn as_key(&self) -> &Key {
+        &self.0
+    }
+}
+
+impl From<Key> for AuthKey {
+    fn from(key: Key) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<[u8; 16]> for AuthKey {
+    fn from(key: [u8; 16]) -> Self {
+        Self::new(key.into())
+    }
+}
+
+impl fmt::Debug for AuthKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuthKey(***)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for AuthKey {
+    fn drop(&mut self) {
+        self.0.as_mut_slice().zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for AuthKey {}
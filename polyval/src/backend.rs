@@ -1,24 +1,127 @@
 //! POLYVAL backends
 
-#[cfg_attr(not(target_pointer_width = "64"), path = "backend/soft32.rs")]
-#[cfg_attr(target_pointer_width = "64", path = "backend/soft64.rs")]
+// The 64-bit implementation is also used on 32-bit targets where 64-bit
+// multiplication is cheap and constant time (currently WebAssembly)
+#[cfg_attr(
+    not(all(
+        any(target_pointer_width = "64", target_arch = "wasm32"),
+        not(polyval_soft32)
+    )),
+    path = "backend/soft32.rs"
+)]
+#[cfg_attr(
+    all(
+        any(target_pointer_width = "64", target_arch = "wasm32"),
+        not(polyval_soft32)
+    ),
+    path = "backend/soft64.rs"
+)]
 mod soft;
 
+pub use crate::backend::soft::Polyval as SoftPolyval;
+
+#[cfg(feature = "table-soft")]
+mod table;
+
+#[cfg(feature = "table-soft")]
+pub use crate::backend::table::TablePolyval;
+
 use cfg_if::cfg_if;
 
+/// Raw state of a POLYVAL backend: `H`, the initial block, the accumulator
+/// and the number of blocks processed.
+#[cfg(feature = "serde")]
+pub(crate) type Parts = (crate::Key, crate::Block, crate::Block, u64);
+
 cfg_if! {
-    if #[cfg(all(target_arch = "aarch64", not(polyval_force_soft)))] {
+    // The PMULL backend assumes little endian lane order, so big endian
+    // AArch64 (`aarch64_be`) uses the portable backend instead
+    if #[cfg(all(
+        target_arch = "aarch64",
+        target_endian = "little",
+        not(any(polyval_force_soft, miri))
+    ))] {
         mod autodetect;
         mod pmull;
         pub use crate::backend::autodetect::Polyval;
+        #[cfg(feature = "hazmat")]
+        pub(crate) use crate::backend::autodetect::{mul_unreduced, reduce};
+    } else if #[cfg(all(
+        any(target_arch = "x86_64", target_arch = "x86"),
+        target_feature = "pclmulqdq",
+        not(any(polyval_force_soft, miri))
+    ))] {
+        // CLMUL is statically enabled (e.g. `-Ctarget-cpu=haswell`), so use
+        // it directly without runtime detection
+        mod clmul;
+        pub use crate::backend::clmul::Polyval;
+        #[cfg(feature = "hazmat")]
+        pub(crate) use crate::backend::clmul::{mul_unreduced, reduce};
     } else if #[cfg(all(
         any(target_arch = "x86_64", target_arch = "x86"),
-        not(polyval_force_soft)
+        not(any(polyval_force_soft, miri))
     ))] {
         mod autodetect;
         mod clmul;
         pub use crate::backend::autodetect::Polyval;
+        #[cfg(feature = "hazmat")]
+        pub(crate) use crate::backend::autodetect::{mul_unreduced, reduce};
     } else {
         pub use crate::backend::soft::Polyval;
+        #[cfg(feature = "hazmat")]
+        pub(crate) use crate::backend::soft::{mul_unreduced, reduce};
     }
 }
+
+/// CPU features detected at runtime which are used by the POLYVAL backends.
+///
+/// Obtained via [`detected_capabilities`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// `CLMUL` (a.k.a. `PCLMULQDQ`) instructions on `x86`/`x86_64`.
+    pub clmul: bool,
+
+    /// `PMULL` instructions on `aarch64`.
+    pub pmull: bool,
+}
+
+/// Get the CPU features which were detected at runtime and will be used by
+/// [`Polyval`].
+///
+/// Detection results are cached, so calling this function is cheap. When the
+/// `polyval_force_soft` cfg is enabled or when running under Miri, no features
+/// are reported as detected.
+pub fn detected_capabilities() -> Capabilities {
+    #[allow(unused_mut)]
+    let mut caps = Capabilities::default();
+
+    #[cfg(all(
+        target_arch = "aarch64",
+        target_endian = "little",
+        not(any(polyval_force_soft, miri))
+    ))]
+    {
+        caps.pmull = autodetect::has_intrinsics();
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86_64", target_arch = "x86"),
+        target_feature = "pclmulqdq",
+        not(any(polyval_force_soft, miri))
+    ))]
+    {
+        caps.clmul = true;
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86_64", target_arch = "x86"),
+        not(target_feature = "pclmulqdq"),
+        not(any(polyval_force_soft, miri))
+    ))]
+    {
+        caps.clmul = autodetect::has_intrinsics();
+    }
+
+    caps
+}
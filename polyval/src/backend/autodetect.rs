@@ -21,7 +21,20 @@ cpufeatures::new!(mul_intrinsics, "aes"); // `aes` implies PMULL
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 cpufeatures::new!(mul_intrinsics, "pclmulqdq");
 
+/// Are the CPU intrinsics used by this backend available?
+pub(crate) fn has_intrinsics() -> bool {
+    mul_intrinsics::get()
+}
+
 /// **POLYVAL**: GHASH-like universal hash over GF(2^128).
+///
+/// The backend is selected by branching on the cached CPU feature detection
+/// result. This branch happens once per call to `update_with_backend` (not
+/// per block), always goes the same way for the lifetime of the process, and
+/// depends only on public information, so it doesn't leak anything about the
+/// key or message. Dispatching via stored function pointers instead would
+/// trade it for an indirect call per block and prevent the backends from
+/// being inlined.
 pub struct Polyval {
     inner: Inner,
     token: mul_intrinsics::InitToken,
@@ -37,7 +50,8 @@ impl KeySizeUser for Polyval {
 }
 
 impl Polyval {
-    /// Initialize POLYVAL with the given `H` field element and initial block
+    /// Initialize POLYVAL with the given `H` field element and initial block,
+    /// which is encoded as little endian (see [`crate::hazmat`])
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
         let (token, has_intrinsics) = mul_intrinsics::init_get();
 
@@ -55,6 +69,95 @@ impl Polyval {
 
         Self { inner, token }
     }
+
+    /// Get the raw state of this instance
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_parts(&self) -> crate::backend::Parts {
+        unsafe {
+            if self.token.get() {
+                self.inner.intrinsics.to_parts()
+            } else {
+                self.inner.soft.to_parts()
+            }
+        }
+    }
+
+    /// Restore an instance from its raw state
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts(parts: &crate::backend::Parts) -> Self {
+        let (token, has_intrinsics) = mul_intrinsics::init_get();
+
+        let inner = if has_intrinsics {
+            Inner {
+                intrinsics: ManuallyDrop::new(intrinsics::Polyval::from_parts(parts)),
+            }
+        } else {
+            Inner {
+                soft: ManuallyDrop::new(soft::Polyval::from_parts(parts)),
+            }
+        };
+
+        Self { inner, token }
+    }
+
+    /// Get the number of blocks processed, saturating at [`u64::MAX`]
+    pub fn block_count(&self) -> u64 {
+        unsafe {
+            if self.token.get() {
+                self.inner.intrinsics.block_count()
+            } else {
+                self.inner.soft.block_count()
+            }
+        }
+    }
+
+    /// Get the number of calls made to each of the block processing methods
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    pub fn proc_stats(&self) -> crate::diagnostics::ProcStats {
+        unsafe {
+            if self.token.get() {
+                self.inner.intrinsics.proc_stats()
+            } else {
+                self.inner.soft.proc_stats()
+            }
+        }
+    }
+
+    /// Reset to the given initial block (encoded as little endian), which
+    /// subsequent calls to [`Reset::reset`] will also restore, keeping `H`.
+    ///
+    /// This avoids reinitializing from the key when only the initial block
+    /// changes between messages.
+    pub fn reset_with_init_block(&mut self, init_block: u128) {
+        unsafe {
+            if self.token.get() {
+                (*self.inner.intrinsics).reset_with_init_block(init_block)
+            } else {
+                (*self.inner.soft).reset_with_init_block(init_block)
+            }
+        }
+    }
+
+    /// Overwrite the accumulator with the given POLYVAL output, e.g. one
+    /// obtained by finalizing a clone, so hashing can resume from it.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// Setting the accumulator to arbitrary values can be used to forge tags.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    pub fn set_accumulator(&mut self, acc: &crate::Block) {
+        unsafe {
+            if self.token.get() {
+                (*self.inner.intrinsics).set_accumulator(acc)
+            } else {
+                (*self.inner.soft).set_accumulator(acc)
+            }
+        }
+    }
 }
 
 impl KeyInit for Polyval {
@@ -119,3 +222,48 @@ impl Reset for Polyval {
         }
     }
 }
+
+opaque_debug::implement!(Polyval);
+
+/// Multiply two field elements, returning the unreduced 256-bit product
+#[cfg(feature = "hazmat")]
+pub(crate) fn mul_unreduced(a: &crate::Block, b: &crate::Block) -> [crate::Block; 2] {
+    if has_intrinsics() {
+        intrinsics::mul_unreduced(a, b)
+    } else {
+        soft::mul_unreduced(a, b)
+    }
+}
+
+/// Reduce a 256-bit product to a field element
+#[cfg(feature = "hazmat")]
+pub(crate) fn reduce(product: &[crate::Block; 2]) -> crate::Block {
+    if has_intrinsics() {
+        intrinsics::reduce(product)
+    } else {
+        soft::reduce(product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polyval;
+    use crate::detected_capabilities;
+    use universal_hash::{KeyInit, UniversalHash};
+
+    #[test]
+    fn capabilities_match_selected_backend() {
+        let polyval = Polyval::new(&Default::default());
+        let caps = detected_capabilities();
+        assert_eq!(polyval.token.get(), caps.clmul || caps.pmull);
+    }
+
+    /// In debug builds the intrinsics backend panics if it's reached without
+    /// CPU support, so this fails if dispatch doesn't respect detection.
+    #[test]
+    fn intrinsics_only_used_when_detected() {
+        let mut polyval = Polyval::new(&Default::default());
+        polyval.update(&[Default::default(); 4]);
+        assert_eq!(polyval.block_count(), 4);
+    }
+}
@@ -9,16 +9,23 @@ use core::arch::x86_64::*;
 use universal_hash::{
     consts::{U1, U16},
     crypto_common::{BlockSizeUser, KeySizeUser, ParBlocksSizeUser},
-    KeyInit, Reset, UhfBackend,
+    KeyInit, Reset, UhfBackend, UhfClosure, UniversalHash,
 };
 
-use crate::{Block, Key, Tag};
+use crate::{hazmat::block_from_u128_le, Block, Key, Tag};
+
+#[cfg(feature = "diagnostics")]
+use {crate::diagnostics::ProcStats, universal_hash::typenum::Unsigned};
 
 /// **POLYVAL**: GHASH-like universal hash over GF(2^128).
 #[derive(Clone)]
 pub struct Polyval {
     h: __m128i,
     y: __m128i,
+    init_block: __m128i,
+    block_count: u64,
+    #[cfg(feature = "diagnostics")]
+    stats: ProcStats,
 }
 
 impl KeySizeUser for Polyval {
@@ -26,17 +33,99 @@ impl KeySizeUser for Polyval {
 }
 
 impl Polyval {
-    /// Initialize POLYVAL with the given `H` field element and initial block
+    /// Initialize POLYVAL with the given `H` field element and initial block,
+    /// which is encoded as little endian (see [`crate::hazmat`])
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
         unsafe {
             // `_mm_loadu_si128` performs an unaligned load
             #[allow(clippy::cast_ptr_alignment)]
+            let init_block =
+                _mm_loadu_si128(block_from_u128_le(init_block).as_ptr() as *const __m128i);
+
+            // `_mm_loadu_si128` performs an unaligned load
+            #[allow(clippy::cast_ptr_alignment)]
+            Self {
+                h: _mm_loadu_si128(h.as_ptr() as *const __m128i),
+                y: init_block,
+                init_block,
+                block_count: 0,
+                #[cfg(feature = "diagnostics")]
+                stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
+            }
+        }
+    }
+
+    /// Get the number of blocks processed, saturating at [`u64::MAX`]
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Get the number of calls made to each of the block processing methods
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    pub fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
+    /// Get the raw state of this instance
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_parts(&self) -> crate::backend::Parts {
+        unsafe {
+            (
+                core::mem::transmute::<__m128i, Block>(self.h),
+                core::mem::transmute::<__m128i, Block>(self.init_block),
+                core::mem::transmute::<__m128i, Block>(self.y),
+                self.block_count,
+            )
+        }
+    }
+
+    /// Restore an instance from its raw state
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts((h, init_block, y, block_count): &crate::backend::Parts) -> Self {
+        // `_mm_loadu_si128` performs an unaligned load
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
             Self {
                 h: _mm_loadu_si128(h.as_ptr() as *const __m128i),
-                y: _mm_loadu_si128(&init_block.to_be_bytes()[..] as *const _ as *const __m128i),
+                y: _mm_loadu_si128(y.as_ptr() as *const __m128i),
+                init_block: _mm_loadu_si128(init_block.as_ptr() as *const __m128i),
+                block_count: *block_count,
+                #[cfg(feature = "diagnostics")]
+                stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
             }
         }
     }
+
+    /// Reset to the given initial block (encoded as little endian), which
+    /// subsequent calls to [`Reset::reset`] will also restore, keeping `H`.
+    pub fn reset_with_init_block(&mut self, init_block: u128) {
+        // `_mm_loadu_si128` performs an unaligned load
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.init_block =
+                _mm_loadu_si128(block_from_u128_le(init_block).as_ptr() as *const __m128i);
+        }
+        self.reset();
+    }
+
+    /// Overwrite the accumulator with the given POLYVAL output, e.g. one
+    /// obtained by finalizing a clone, so hashing can resume from it.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// Setting the accumulator to arbitrary values can be used to forge tags.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    pub fn set_accumulator(&mut self, acc: &Block) {
+        // `_mm_loadu_si128` performs an unaligned load
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.y = _mm_loadu_si128(acc.as_ptr() as *const __m128i);
+        }
+    }
 }
 
 impl KeyInit for Polyval {
@@ -56,15 +145,33 @@ impl ParBlocksSizeUser for Polyval {
 
 impl UhfBackend for Polyval {
     fn proc_block(&mut self, x: &Block) {
+        // Catch this backend being selected on a CPU without CLMUL, which
+        // would otherwise crash with SIGILL
+        #[cfg(not(target_feature = "pclmulqdq"))]
+        debug_assert!(
+            super::autodetect::has_intrinsics(),
+            "CLMUL backend used without CPU support"
+        );
+
         unsafe {
             self.mul(x);
         }
+        self.block_count = self.block_count.saturating_add(1);
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
     }
 }
 
-impl Polyval {
-    /// Get GHASH output
-    pub(crate) fn finalize(self) -> Tag {
+impl UniversalHash for Polyval {
+    fn update_with_backend(&mut self, f: impl UhfClosure<BlockSize = Self::BlockSize>) {
+        f.call(self);
+    }
+
+    /// Get POLYVAL result (i.e. computed `S` field element)
+    fn finalize(self) -> Tag {
         unsafe { core::mem::transmute(self.y) }
     }
 }
@@ -73,79 +180,140 @@ impl Polyval {
     #[inline]
     #[target_feature(enable = "pclmulqdq")]
     unsafe fn mul(&mut self, x: &Block) {
-        let h = self.h;
-
         // `_mm_loadu_si128` performs an unaligned load
         #[allow(clippy::cast_ptr_alignment)]
         let x = _mm_loadu_si128(x.as_ptr() as *const __m128i);
-        let y = _mm_xor_si128(self.y, x);
-
-        let h0 = h;
-        let h1 = _mm_shuffle_epi32(h, 0x0E);
-        let h2 = _mm_xor_si128(h0, h1);
-        let y0 = y;
-
-        // Multiply values partitioned to 64-bit parts
-        let y1 = _mm_shuffle_epi32(y, 0x0E);
-        let y2 = _mm_xor_si128(y0, y1);
-        let t0 = _mm_clmulepi64_si128(y0, h0, 0x00);
-        let t1 = _mm_clmulepi64_si128(y, h, 0x11);
-        let t2 = _mm_clmulepi64_si128(y2, h2, 0x00);
-        let t2 = _mm_xor_si128(t2, _mm_xor_si128(t0, t1));
-        let v0 = t0;
-        let v1 = _mm_xor_si128(_mm_shuffle_epi32(t0, 0x0E), t2);
-        let v2 = _mm_xor_si128(t1, _mm_shuffle_epi32(t2, 0x0E));
-        let v3 = _mm_shuffle_epi32(t1, 0x0E);
-
-        // Polynomial reduction
-        let v2 = xor5(
-            v2,
-            v0,
-            _mm_srli_epi64(v0, 1),
-            _mm_srli_epi64(v0, 2),
-            _mm_srli_epi64(v0, 7),
-        );
+        let (lo, hi) = karatsuba(_mm_xor_si128(self.y, x), self.h);
+        self.y = mont_reduce(lo, hi);
+    }
+}
 
-        let v1 = xor4(
-            v1,
-            _mm_slli_epi64(v0, 63),
-            _mm_slli_epi64(v0, 62),
-            _mm_slli_epi64(v0, 57),
-        );
+/// Multiply two field elements, returning the unreduced 256-bit product
+#[cfg(feature = "hazmat")]
+pub(crate) fn mul_unreduced(a: &Block, b: &Block) -> [Block; 2] {
+    #[cfg(not(target_feature = "pclmulqdq"))]
+    debug_assert!(
+        super::autodetect::has_intrinsics(),
+        "CLMUL backend used without CPU support"
+    );
 
-        let v3 = xor5(
-            v3,
-            v1,
-            _mm_srli_epi64(v1, 1),
-            _mm_srli_epi64(v1, 2),
-            _mm_srli_epi64(v1, 7),
+    // `_mm_loadu_si128` performs an unaligned load
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+        let (lo, hi) = karatsuba(
+            _mm_loadu_si128(a.as_ptr() as *const __m128i),
+            _mm_loadu_si128(b.as_ptr() as *const __m128i),
         );
+        [
+            core::mem::transmute::<__m128i, Block>(lo),
+            core::mem::transmute::<__m128i, Block>(hi),
+        ]
+    }
+}
 
-        let v2 = xor4(
-            v2,
-            _mm_slli_epi64(v1, 63),
-            _mm_slli_epi64(v1, 62),
-            _mm_slli_epi64(v1, 57),
-        );
+/// Reduce a 256-bit product to a field element
+#[cfg(feature = "hazmat")]
+pub(crate) fn reduce(product: &[Block; 2]) -> Block {
+    #[cfg(not(target_feature = "pclmulqdq"))]
+    debug_assert!(
+        super::autodetect::has_intrinsics(),
+        "CLMUL backend used without CPU support"
+    );
 
-        self.y = _mm_unpacklo_epi64(v2, v3);
+    // `_mm_loadu_si128` performs an unaligned load
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+        core::mem::transmute(mont_reduce(
+            _mm_loadu_si128(product[0].as_ptr() as *const __m128i),
+            _mm_loadu_si128(product[1].as_ptr() as *const __m128i),
+        ))
     }
 }
 
+/// Karatsuba multiplication of `y` and `h`, returning the low and high halves
+/// of the 256-bit carryless product.
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn karatsuba(y: __m128i, h: __m128i) -> (__m128i, __m128i) {
+    let h0 = h;
+    let h1 = _mm_shuffle_epi32(h, 0x0E);
+    let h2 = _mm_xor_si128(h0, h1);
+    let y0 = y;
+
+    // Multiply values partitioned to 64-bit parts
+    let y1 = _mm_shuffle_epi32(y, 0x0E);
+    let y2 = _mm_xor_si128(y0, y1);
+    let t0 = _mm_clmulepi64_si128(y0, h0, 0x00);
+    let t1 = _mm_clmulepi64_si128(y, h, 0x11);
+    let t2 = _mm_clmulepi64_si128(y2, h2, 0x00);
+    let t2 = _mm_xor_si128(t2, _mm_xor_si128(t0, t1));
+
+    (
+        _mm_xor_si128(t0, _mm_slli_si128(t2, 8)),
+        _mm_xor_si128(t1, _mm_srli_si128(t2, 8)),
+    )
+}
+
+/// Montgomery reduction of the 256-bit product `hi:lo`.
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn mont_reduce(lo: __m128i, hi: __m128i) -> __m128i {
+    // Only the low 64 bits of each of these are used
+    let v0 = lo;
+    let v1 = _mm_shuffle_epi32(lo, 0x0E);
+    let v2 = hi;
+    let v3 = _mm_shuffle_epi32(hi, 0x0E);
+
+    // Polynomial reduction
+    let v2 = xor5(
+        v2,
+        v0,
+        _mm_srli_epi64(v0, 1),
+        _mm_srli_epi64(v0, 2),
+        _mm_srli_epi64(v0, 7),
+    );
+
+    let v1 = xor4(
+        v1,
+        _mm_slli_epi64(v0, 63),
+        _mm_slli_epi64(v0, 62),
+        _mm_slli_epi64(v0, 57),
+    );
+
+    let v3 = xor5(
+        v3,
+        v1,
+        _mm_srli_epi64(v1, 1),
+        _mm_srli_epi64(v1, 2),
+        _mm_srli_epi64(v1, 7),
+    );
+
+    let v2 = xor4(
+        v2,
+        _mm_slli_epi64(v1, 63),
+        _mm_slli_epi64(v1, 62),
+        _mm_slli_epi64(v1, 57),
+    );
+
+    _mm_unpacklo_epi64(v2, v3)
+}
+
 impl Reset for Polyval {
     fn reset(&mut self) {
-        unsafe {
-            self.y = _mm_setzero_si128();
-        }
+        self.y = self.init_block;
+        self.block_count = 0;
     }
 }
 
+opaque_debug::implement!(Polyval);
+
 #[cfg(feature = "zeroize")]
 impl Drop for Polyval {
     fn drop(&mut self) {
         use zeroize::Zeroize;
         self.h.zeroize();
         self.y.zeroize();
+        self.init_block.zeroize();
     }
 }
 
@@ -161,3 +329,18 @@ unsafe fn xor5(e1: __m128i, e2: __m128i, e3: __m128i, e4: __m128i, e5: __m128i)
         _mm_xor_si128(_mm_xor_si128(e2, e3), _mm_xor_si128(e4, e5)),
     )
 }
+
+#[cfg(all(test, target_feature = "pclmulqdq"))]
+mod tests {
+    use core::any::TypeId;
+
+    /// With `CLMUL` statically enabled, this backend is used directly rather
+    /// than via runtime detection.
+    #[test]
+    fn selected_statically() {
+        assert_eq!(
+            TypeId::of::<crate::Polyval>(),
+            TypeId::of::<super::Polyval>()
+        );
+    }
+}
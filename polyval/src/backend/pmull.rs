@@ -19,7 +19,10 @@ use universal_hash::{
     KeyInit, Reset, UhfBackend,
 };
 
-use crate::{Block, Key, Tag};
+use crate::{hazmat::block_from_u128_le, Block, Key, Tag};
+
+#[cfg(feature = "diagnostics")]
+use {crate::diagnostics::ProcStats, universal_hash::typenum::Unsigned};
 
 /// Montgomery reduction polynomial
 const POLY: u128 = (1 << 127) | (1 << 126) | (1 << 121) | (1 << 63) | (1 << 62) | (1 << 57);
@@ -29,6 +32,10 @@ const POLY: u128 = (1 << 127) | (1 << 126) | (1 << 121) | (1 << 63) | (1 << 62)
 pub struct Polyval {
     h: uint8x16_t,
     y: uint8x16_t,
+    init_block: uint8x16_t,
+    block_count: u64,
+    #[cfg(feature = "diagnostics")]
+    stats: ProcStats,
 }
 
 impl KeySizeUser for Polyval {
@@ -36,15 +43,77 @@ impl KeySizeUser for Polyval {
 }
 
 impl Polyval {
-    /// Initialize POLYVAL with the given `H` field element and initial block
+    /// Initialize POLYVAL with the given `H` field element and initial block,
+    /// which is encoded as little endian (see [`crate::hazmat`])
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
+        unsafe {
+            let init_block = vld1q_u8(block_from_u128_le(init_block).as_ptr());
+
+            Self {
+                h: vld1q_u8(h.as_ptr()),
+                y: init_block,
+                init_block,
+                block_count: 0,
+                #[cfg(feature = "diagnostics")]
+                stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
+            }
+        }
+    }
+
+    /// Get the number of blocks processed
+    pub(crate) fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Get the number of calls made to each of the block processing methods
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
+    /// Get the raw state of this instance
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_parts(&self) -> crate::backend::Parts {
+        unsafe {
+            (
+                mem::transmute::<uint8x16_t, Block>(self.h),
+                mem::transmute::<uint8x16_t, Block>(self.init_block),
+                mem::transmute::<uint8x16_t, Block>(self.y),
+                self.block_count,
+            )
+        }
+    }
+
+    /// Restore an instance from its raw state
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_parts((h, init_block, y, block_count): &crate::backend::Parts) -> Self {
         unsafe {
             Self {
                 h: vld1q_u8(h.as_ptr()),
-                y: vld1q_u8(init_block.to_be_bytes()[..].as_ptr()),
+                y: vld1q_u8(y.as_ptr()),
+                init_block: vld1q_u8(init_block.as_ptr()),
+                block_count: *block_count,
+                #[cfg(feature = "diagnostics")]
+                stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
             }
         }
     }
+
+    /// Reset to the given initial block, keeping `H`
+    pub(crate) fn reset_with_init_block(&mut self, init_block: u128) {
+        unsafe {
+            self.init_block = vld1q_u8(block_from_u128_le(init_block).as_ptr());
+        }
+        self.reset();
+    }
+
+    /// Overwrite the accumulator with the given POLYVAL output
+    #[cfg(feature = "hazmat")]
+    pub(crate) fn set_accumulator(&mut self, acc: &Block) {
+        unsafe {
+            self.y = vld1q_u8(acc.as_ptr());
+        }
+    }
 }
 
 impl KeyInit for Polyval {
@@ -64,17 +133,29 @@ impl ParBlocksSizeUser for Polyval {
 
 impl UhfBackend for Polyval {
     fn proc_block(&mut self, x: &Block) {
+        // Catch this backend being selected on a CPU without PMULL, which
+        // would otherwise crash with SIGILL
+        debug_assert!(
+            super::autodetect::has_intrinsics(),
+            "PMULL backend used without CPU support"
+        );
+
         unsafe {
             self.mul(x);
         }
+        self.block_count = self.block_count.saturating_add(1);
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
     }
 }
 
 impl Reset for Polyval {
     fn reset(&mut self) {
-        unsafe {
-            self.y = vdupq_n_u8(0);
-        }
+        self.y = self.init_block;
+        self.block_count = 0;
     }
 }
 
@@ -96,6 +177,40 @@ impl Polyval {
     }
 }
 
+/// Multiply two field elements, returning the unreduced 256-bit product
+#[cfg(feature = "hazmat")]
+pub(crate) fn mul_unreduced(a: &Block, b: &Block) -> [Block; 2] {
+    debug_assert!(
+        super::autodetect::has_intrinsics(),
+        "PMULL backend used without CPU support"
+    );
+
+    unsafe {
+        let (h, m, l) = karatsuba1(vld1q_u8(a.as_ptr()), vld1q_u8(b.as_ptr()));
+        let (hi, lo) = karatsuba2(h, m, l);
+        [
+            mem::transmute::<uint8x16_t, Block>(lo),
+            mem::transmute::<uint8x16_t, Block>(hi),
+        ]
+    }
+}
+
+/// Reduce a 256-bit product to a field element
+#[cfg(feature = "hazmat")]
+pub(crate) fn reduce(product: &[Block; 2]) -> Block {
+    debug_assert!(
+        super::autodetect::has_intrinsics(),
+        "PMULL backend used without CPU support"
+    );
+
+    unsafe {
+        mem::transmute(mont_reduce(
+            vld1q_u8(product[1].as_ptr()),
+            vld1q_u8(product[0].as_ptr()),
+        ))
+    }
+}
+
 /// Karatsuba decomposition for `x*y`.
 #[inline]
 #[target_feature(enable = "neon")]
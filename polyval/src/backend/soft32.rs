@@ -25,7 +25,7 @@
 //! In other words, if we bit-reverse (over 32 bits) the operands, then we
 //! bit-reverse (over 64 bits) the result.
 
-use crate::{Block, Key, Tag};
+use crate::{hazmat::block_from_u128_le, Block, Key, Tag};
 use core::{
     num::Wrapping,
     ops::{Add, Mul},
@@ -36,6 +36,9 @@ use universal_hash::{
     KeyInit, Reset, UhfBackend, UhfClosure, UniversalHash,
 };
 
+#[cfg(feature = "diagnostics")]
+use {crate::diagnostics::ProcStats, universal_hash::typenum::Unsigned};
+
 #[cfg(feature = "zeroize")]
 use zeroize::Zeroize;
 
@@ -47,6 +50,16 @@ pub struct Polyval {
 
     /// Field element representing the computed universal hash
     s: U32x4,
+
+    /// Initial block the computed universal hash is reset to
+    init_block: U32x4,
+
+    /// Number of blocks processed
+    block_count: u64,
+
+    /// Calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    stats: ProcStats,
 }
 
 impl KeySizeUser for Polyval {
@@ -54,13 +67,77 @@ impl KeySizeUser for Polyval {
 }
 
 impl Polyval {
-    /// Initialize POLYVAL with the given `H` field element and initial block
+    /// Initialize POLYVAL with the given `H` field element and initial block,
+    /// which is encoded as little endian (see [`crate::hazmat`])
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
         Self {
             h: h.into(),
             s: init_block.into(),
+            init_block: init_block.into(),
+            block_count: 0,
+            #[cfg(feature = "diagnostics")]
+            stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
         }
     }
+
+    /// Get the number of blocks processed, saturating at [`u64::MAX`]
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Get the number of calls made to each of the block processing methods
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    pub fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
+    /// Get the raw state of this instance
+    #[cfg(feature = "serde")]
+    #[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+    pub(crate) fn to_parts(&self) -> crate::backend::Parts {
+        (
+            self.h.into(),
+            self.init_block.into(),
+            self.s.into(),
+            self.block_count,
+        )
+    }
+
+    /// Restore an instance from its raw state
+    #[cfg(feature = "serde")]
+    #[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+    pub(crate) fn from_parts((h, init_block, s, block_count): &crate::backend::Parts) -> Self {
+        Self {
+            h: h.into(),
+            s: s.into(),
+            init_block: init_block.into(),
+            block_count: *block_count,
+            #[cfg(feature = "diagnostics")]
+            stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
+        }
+    }
+
+    /// Reset to the given initial block (encoded as little endian), which
+    /// subsequent calls to [`Reset::reset`] will also restore, keeping `H`.
+    pub fn reset_with_init_block(&mut self, init_block: u128) {
+        self.init_block = init_block.into();
+        self.reset();
+    }
+
+    /// Overwrite the accumulator with the given POLYVAL output, e.g. one
+    /// obtained by finalizing a clone, so hashing can resume from it.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// Setting the accumulator to arbitrary values can be used to forge tags.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    pub fn set_accumulator(&mut self, acc: &Block) {
+        self.s = acc.into();
+    }
 }
 
 impl KeyInit for Polyval {
@@ -82,6 +159,12 @@ impl UhfBackend for Polyval {
     fn proc_block(&mut self, x: &Block) {
         let x = U32x4::from(x);
         self.s = (self.s + x) * self.h;
+        self.block_count = self.block_count.saturating_add(1);
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
     }
 }
 
@@ -92,30 +175,45 @@ impl UniversalHash for Polyval {
 
     /// Get POLYVAL result (i.e. computed `S` field element)
     fn finalize(self) -> Tag {
-        let mut block = Block::default();
-
-        for (chunk, i) in block
-            .chunks_mut(4)
-            .zip(&[self.s.0, self.s.1, self.s.2, self.s.3])
-        {
-            chunk.copy_from_slice(&i.to_le_bytes());
-        }
-
-        block
+        self.s.into()
     }
 }
 
 impl Reset for Polyval {
     fn reset(&mut self) {
-        self.s = U32x4::default();
+        self.s = self.init_block;
+        self.block_count = 0;
     }
 }
 
+opaque_debug::implement!(Polyval);
+
+/// Multiply two field elements, returning the unreduced 256-bit product
+#[cfg(feature = "hazmat")]
+#[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+pub(crate) fn mul_unreduced(a: &Block, b: &Block) -> [Block; 2] {
+    let U32x8(zw) = U32x4::from(a).karatsuba_unreduced(U32x4::from(b));
+    [
+        U32x4(zw[0], zw[1], zw[2], zw[3]).into(),
+        U32x4(zw[4], zw[5], zw[6], zw[7]).into(),
+    ]
+}
+
+/// Reduce a 256-bit product to a field element
+#[cfg(feature = "hazmat")]
+#[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+pub(crate) fn reduce(product: &[Block; 2]) -> Block {
+    let U32x4(z0, z1, z2, z3) = U32x4::from(&product[0]);
+    let U32x4(z4, z5, z6, z7) = U32x4::from(&product[1]);
+    U32x8([z0, z1, z2, z3, z4, z5, z6, z7]).reduce().into()
+}
+
 #[cfg(feature = "zeroize")]
 impl Drop for Polyval {
     fn drop(&mut self) {
         self.h.zeroize();
         self.s.zeroize();
+        self.init_block.zeroize();
     }
 }
 
@@ -134,14 +232,21 @@ impl From<&Block> for U32x4 {
     }
 }
 
+impl From<U32x4> for Block {
+    fn from(x: U32x4) -> Block {
+        let mut block = Block::default();
+
+        for (chunk, i) in block.chunks_mut(4).zip(&[x.0, x.1, x.2, x.3]) {
+            chunk.copy_from_slice(&i.to_le_bytes());
+        }
+
+        block
+    }
+}
+
 impl From<u128> for U32x4 {
     fn from(x: u128) -> Self {
-        U32x4(
-            x as u32,
-            (x >> 32) as u32,
-            (x >> 64) as u32,
-            (x >> 96) as u32,
-        )
+        U32x4::from(&block_from_u128_le(x))
     }
 }
 
@@ -182,6 +287,14 @@ impl Mul for U32x4 {
     ///
     /// This shift is unnecessary for POLYVAL and has been removed.
     fn mul(self, rhs: Self) -> Self {
+        self.karatsuba_unreduced(rhs).reduce()
+    }
+}
+
+impl U32x4 {
+    /// Computes the carryless product of two POLYVAL field elements using
+    /// Karatsuba multiplication, without the final polynomial reduction.
+    fn karatsuba_unreduced(self, rhs: Self) -> U32x8 {
         let hw = [self.0, self.1, self.2, self.3];
         let yw = [rhs.0, rhs.1, rhs.2, rhs.3];
         let hwr = [rev32(hw[0]), rev32(hw[1]), rev32(hw[2]), rev32(hw[3])];
@@ -258,6 +371,19 @@ impl Mul for U32x4 {
         zw[6] = c[3] ^ rev32(c[14]) >> 1;
         zw[7] = rev32(c[12]) >> 1;
 
+        U32x8(zw)
+    }
+}
+
+/// 8 x `u32` values: an unreduced 256-bit carryless product
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+struct U32x8([u32; 8]);
+
+impl U32x8 {
+    /// Reduces a 256-bit carryless product to a POLYVAL field element.
+    fn reduce(self) -> U32x4 {
+        let U32x8(mut zw) = self;
+
         for i in 0..4 {
             let lw = zw[i];
             zw[i + 4] ^= lw ^ (lw >> 1) ^ (lw >> 2) ^ (lw >> 7);
@@ -314,3 +440,51 @@ fn rev32(mut x: u32) -> u32 {
     x = ((x & 0x00ff_00ff) << 8) | (x >> 8 & 0x00ff_00ff);
     x.rotate_right(16)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Polyval, U32x4, U32x8};
+    use crate::Block;
+    use universal_hash::{KeyInit, Reset, UniversalHash};
+
+    #[test]
+    fn block_count_saturates() {
+        let mut poly = Polyval::new(&Block::default());
+        poly.block_count = u64::MAX - 1;
+
+        poly.update(&[Block::default(); 3]);
+        assert_eq!(poly.block_count(), u64::MAX);
+
+        poly.reset();
+        assert_eq!(poly.block_count(), 0);
+    }
+
+    #[test]
+    fn aggregated_reduction() {
+        let xs = [
+            U32x4(0x89ab_cdef, 0x0123_4567, 0x7654_3210, 0xfedc_ba98),
+            U32x4(u32::MAX, u32::MAX, 1, 0),
+            U32x4(0, 0x8000_0000, 1, 0),
+            U32x4(0xcafe_babe, 0xdead_beef, 0xd00d_feed, 0x0bad_f00d),
+        ];
+        let hs = [
+            U32x4(0x5892_4276, 0x2562_9347, 0xba4b_757b, 0x1d31_f826),
+            U32x4(3, 0, u32::MAX, u32::MAX),
+            U32x4(0x8c83_dfb6, 0x4f4f_9566, 0x2d01_a262, 0x4017_62bb),
+            U32x4(0x2721_d006, 0xd1a2_4ddd, 0xd3c9_f362, 0xbbe4_5f20),
+        ];
+
+        let mut unreduced = U32x8::default();
+        let mut expected = U32x4::default();
+
+        for (&x, &h) in xs.iter().zip(&hs) {
+            let U32x8(p) = x.karatsuba_unreduced(h);
+            for (acc, word) in unreduced.0.iter_mut().zip(p) {
+                *acc ^= word;
+            }
+            expected = expected + x * h;
+        }
+
+        assert_eq!(unreduced.reduce(), expected);
+    }
+}
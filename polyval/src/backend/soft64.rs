@@ -19,7 +19,10 @@ use universal_hash::{
 #[cfg(feature = "zeroize")]
 use zeroize::Zeroize;
 
-use crate::{Block, Key, Tag};
+use crate::{hazmat::block_from_u128_le, Block, Key, Tag};
+
+#[cfg(feature = "diagnostics")]
+use {crate::diagnostics::ProcStats, universal_hash::typenum::Unsigned};
 
 /// **POLYVAL**: GHASH-like universal hash over GF(2^128).
 #[derive(Clone)]
@@ -29,16 +32,90 @@ pub struct Polyval {
 
     /// Field element representing the computed universal hash
     s: U64x2,
+
+    /// Initial block the computed universal hash is reset to
+    init_block: U64x2,
+
+    /// Number of blocks processed
+    block_count: u64,
+
+    /// Calls made to the block processing methods
+    #[cfg(feature = "diagnostics")]
+    stats: ProcStats,
 }
 
 impl Polyval {
-    /// Initialize POLYVAL with the given `H` field element and initial block
+    /// Initialize POLYVAL with the given `H` field element and initial block,
+    /// which is encoded as little endian (see [`crate::hazmat`])
     pub fn new_with_init_block(h: &Key, init_block: u128) -> Self {
         Self {
             h: h.into(),
             s: init_block.into(),
+            init_block: init_block.into(),
+            block_count: 0,
+            #[cfg(feature = "diagnostics")]
+            stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
+        }
+    }
+
+    /// Get the number of blocks processed, saturating at [`u64::MAX`]
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Get the number of calls made to each of the block processing methods
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    pub fn proc_stats(&self) -> ProcStats {
+        self.stats
+    }
+
+    /// Get the raw state of this instance
+    #[cfg(feature = "serde")]
+    #[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+    pub(crate) fn to_parts(&self) -> crate::backend::Parts {
+        (
+            self.h.into(),
+            self.init_block.into(),
+            self.s.into(),
+            self.block_count,
+        )
+    }
+
+    /// Restore an instance from its raw state
+    #[cfg(feature = "serde")]
+    #[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+    pub(crate) fn from_parts((h, init_block, s, block_count): &crate::backend::Parts) -> Self {
+        Self {
+            h: h.into(),
+            s: s.into(),
+            init_block: init_block.into(),
+            block_count: *block_count,
+            #[cfg(feature = "diagnostics")]
+            stats: ProcStats::new(<Self as ParBlocksSizeUser>::ParBlocksSize::USIZE),
         }
     }
+
+    /// Reset to the given initial block (encoded as little endian), which
+    /// subsequent calls to [`Reset::reset`] will also restore, keeping `H`.
+    pub fn reset_with_init_block(&mut self, init_block: u128) {
+        self.init_block = init_block.into();
+        self.reset();
+    }
+
+    /// Overwrite the accumulator with the given POLYVAL output, e.g. one
+    /// obtained by finalizing a clone, so hashing can resume from it.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// Setting the accumulator to arbitrary values can be used to forge tags.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    pub fn set_accumulator(&mut self, acc: &Block) {
+        self.s = acc.into();
+    }
 }
 
 impl KeySizeUser for Polyval {
@@ -64,6 +141,12 @@ impl UhfBackend for Polyval {
     fn proc_block(&mut self, x: &Block) {
         let x = U64x2::from(x);
         self.s = (self.s + x) * self.h;
+        self.block_count = self.block_count.saturating_add(1);
+
+        #[cfg(feature = "diagnostics")]
+        {
+            self.stats.single_calls += 1;
+        }
     }
 }
 
@@ -74,27 +157,42 @@ impl UniversalHash for Polyval {
 
     /// Get POLYVAL result (i.e. computed `S` field element)
     fn finalize(self) -> Tag {
-        let mut block = Block::default();
-
-        for (chunk, i) in block.chunks_mut(8).zip(&[self.s.0, self.s.1]) {
-            chunk.copy_from_slice(&i.to_le_bytes());
-        }
-
-        block
+        self.s.into()
     }
 }
 
 impl Reset for Polyval {
     fn reset(&mut self) {
-        self.s = U64x2::default();
+        self.s = self.init_block;
+        self.block_count = 0;
     }
 }
 
+opaque_debug::implement!(Polyval);
+
+/// Multiply two field elements, returning the unreduced 256-bit product
+#[cfg(feature = "hazmat")]
+#[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+pub(crate) fn mul_unreduced(a: &Block, b: &Block) -> [Block; 2] {
+    let U64x4(v0, v1, v2, v3) = U64x2::from(a).karatsuba_unreduced(U64x2::from(b));
+    [U64x2(v0, v1).into(), U64x2(v2, v3).into()]
+}
+
+/// Reduce a 256-bit product to a field element
+#[cfg(feature = "hazmat")]
+#[allow(dead_code)] // unused when the CLMUL backend is statically enabled
+pub(crate) fn reduce(product: &[Block; 2]) -> Block {
+    let U64x2(v0, v1) = U64x2::from(&product[0]);
+    let U64x2(v2, v3) = U64x2::from(&product[1]);
+    U64x4(v0, v1, v2, v3).reduce().into()
+}
+
 #[cfg(feature = "zeroize")]
 impl Drop for Polyval {
     fn drop(&mut self) {
         self.h.zeroize();
         self.s.zeroize();
+        self.init_block.zeroize();
     }
 }
 
@@ -111,9 +209,21 @@ impl From<&Block> for U64x2 {
     }
 }
 
+impl From<U64x2> for Block {
+    fn from(x: U64x2) -> Block {
+        let mut block = Block::default();
+
+        for (chunk, i) in block.chunks_mut(8).zip(&[x.0, x.1]) {
+            chunk.copy_from_slice(&i.to_le_bytes());
+        }
+
+        block
+    }
+}
+
 impl From<u128> for U64x2 {
     fn from(x: u128) -> Self {
-        U64x2((x >> 64) as u64, (x) as u64)
+        U64x2::from(&block_from_u128_le(x))
     }
 }
 
@@ -149,6 +259,17 @@ impl Mul for U64x2 {
     ///
     /// This shift is unnecessary for POLYVAL and has been removed.
     fn mul(self, rhs: Self) -> Self {
+        self.karatsuba_unreduced(rhs).reduce()
+    }
+}
+
+impl U64x2 {
+    /// Computes the carryless product of two POLYVAL field elements using
+    /// Karatsuba multiplication, without the final polynomial reduction.
+    ///
+    /// Reduction is linear, so unreduced products can be XORed together and
+    /// reduced once with [`U64x4::reduce`].
+    fn karatsuba_unreduced(self, rhs: Self) -> U64x4 {
         let h0 = self.0;
         let h1 = self.1;
         let h0r = rev64(h0);
@@ -176,10 +297,18 @@ impl Mul for U64x2 {
         z1h = rev64(z1h) >> 1;
         z2h = rev64(z2h) >> 1;
 
-        let v0 = z0;
-        let mut v1 = z0h ^ z2;
-        let mut v2 = z1 ^ z2h;
-        let mut v3 = z1h;
+        U64x4(z0, z0h ^ z2, z1 ^ z2h, z1h)
+    }
+}
+
+/// 4 x `u64` values: an unreduced 256-bit carryless product
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+struct U64x4(u64, u64, u64, u64);
+
+impl U64x4 {
+    /// Reduces a 256-bit carryless product to a POLYVAL field element.
+    fn reduce(self) -> U64x2 {
+        let U64x4(v0, mut v1, mut v2, mut v3) = self;
 
         v2 ^= v0 ^ (v0 >> 1) ^ (v0 >> 2) ^ (v0 >> 7);
         v1 ^= (v0 << 63) ^ (v0 << 62) ^ (v0 << 57);
@@ -235,3 +364,54 @@ fn rev64(mut x: u64) -> u64 {
     x = ((x & 0xffff_0000_ffff) << 16) | ((x >> 16) & 0xffff_0000_ffff);
     x.rotate_right(32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Polyval, U64x2, U64x4};
+    use crate::Block;
+    use universal_hash::{KeyInit, Reset, UniversalHash};
+
+    #[test]
+    fn block_count_saturates() {
+        let mut poly = Polyval::new(&Block::default());
+        poly.block_count = u64::MAX - 1;
+
+        poly.update(&[Block::default(); 3]);
+        assert_eq!(poly.block_count(), u64::MAX);
+
+        poly.reset();
+        assert_eq!(poly.block_count(), 0);
+    }
+
+    #[test]
+    fn aggregated_reduction() {
+        let xs = [
+            U64x2(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+            U64x2(u64::MAX, 1),
+            U64x2(0x8000_0000_0000_0000, 0x0000_0000_0000_0001),
+            U64x2(0xdead_beef_cafe_babe, 0x0bad_f00d_d00d_feed),
+        ];
+        let hs = [
+            U64x2(0x2562_9347_5892_4276, 0x1d31_f826_ba4b_757b),
+            U64x2(3, u64::MAX),
+            U64x2(0x4f4f_9566_8c83_dfb6, 0x4017_62bb_2d01_a262),
+            U64x2(0xd1a2_4ddd_2721_d006, 0xbbe4_5f20_d3c9_f362),
+        ];
+
+        let mut unreduced = U64x4::default();
+        let mut expected = U64x2::default();
+
+        for (&x, &h) in xs.iter().zip(&hs) {
+            let p = x.karatsuba_unreduced(h);
+            unreduced = U64x4(
+                unreduced.0 ^ p.0,
+                unreduced.1 ^ p.1,
+                unreduced.2 ^ p.2,
+                unreduced.3 ^ p.3,
+            );
+            expected = expected + x * h;
+        }
+
+        assert_eq!(unreduced.reduce(), expected);
+    }
+}
@@ -0,0 +1,215 @@
+//! Table-based software implementation of POLYVAL using Shoup's 4-bit method.
+//!
+//! # ⚠️ Warning: not constant time!
+//!
+//! This implementation performs lookups into a table indexed by secret data,
+//! which leaks information about the input blocks and the key via cache
+//! timing side channels. It's only suitable for platforms where such side
+//! channels are considered acceptable (e.g. microcontrollers without a data
+//! cache) and performance is more important than side-channel resistance.
+//!
+//! Internally this computes GHASH using the identity from RFC 8452 Appendix A:
+//!
+//! ```text
+//! POLYVAL(H, X_1, ..., X_n) =
+//!   ByteReverse(GHASH(mulX_GHASH(ByteReverse(H)), ByteReverse(X_1), ..., ByteReverse(X_n)))
+//! ```
+//!
+//! Adapted from Go's `crypto/cipher` generic GCM implementation.
+
+use universal_hash::{
+    consts::{U1, U16},
+    crypto_common::{BlockSizeUser, KeySizeUser, ParBlocksSizeUser},
+    KeyInit, Reset, UhfBackend, UhfClosure, UniversalHash,
+};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::{Block, Key, Tag};
+
+/// Reduction constants for the 4 bits shifted out during multiplication.
+const REDUCTION_TABLE: [u16; 16] = [
+    0x0000, 0x1c20, 0x3840, 0x2460, 0x7080, 0x6ca0, 0x48c0, 0x54e0, 0xe100, 0xfd20, 0xd940, 0xc560,
+    0x9180, 0x8da0, 0xa9c0, 0xb5e0,
+];
+
+/// **POLYVAL** implemented using a precomputed 4-bit multiplication table.
+///
+/// # ⚠️ Warning: not constant time!
+///
+/// Unlike [`Polyval`][`crate::Polyval`], this implementation is vulnerable to
+/// cache timing side-channel attacks which can recover the key, as it looks
+/// up entries in a key-dependent table using indices derived from secret data.
+///
+/// It's intended for platforms lacking a carryless multiply instruction where
+/// the performance of the constant-time software backend is insufficient and
+/// the side-channel risk has been deliberately accepted. Otherwise use
+/// [`Polyval`][`crate::Polyval`].
+#[derive(Clone)]
+pub struct TablePolyval {
+    /// Multiples of the GHASH-domain `H` field element indexed by bit-reversed
+    /// nibble (256 bytes)
+    table: [FieldElement; 16],
+
+    /// Field element representing the computed universal hash (GHASH domain)
+    y: FieldElement,
+}
+
+impl KeySizeUser for TablePolyval {
+    type KeySize = U16;
+}
+
+impl KeyInit for TablePolyval {
+    /// Initialize POLYVAL with the given `H` field element
+    fn new(h: &Key) -> Self {
+        let mut h_ghash = *h;
+        h_ghash.reverse();
+        let x = FieldElement::from_be_bytes(&h_ghash).double();
+
+        let mut table = [FieldElement::default(); 16];
+        table[reverse_bits(1)] = x;
+
+        for i in (2..16).step_by(2) {
+            table[reverse_bits(i)] = table[reverse_bits(i / 2)].double();
+            table[reverse_bits(i + 1)] = table[reverse_bits(i)].add(&x);
+        }
+
+        #[cfg(feature = "zeroize")]
+        h_ghash.zeroize();
+
+        Self {
+            table,
+            y: FieldElement::default(),
+        }
+    }
+}
+
+impl BlockSizeUser for TablePolyval {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for TablePolyval {
+    type ParBlocksSize = U1;
+}
+
+impl UhfBackend for TablePolyval {
+    fn proc_block(&mut self, x: &Block) {
+        let mut x = *x;
+        x.reverse();
+        self.y = self.y.add(&FieldElement::from_be_bytes(&x));
+        self.mul_h();
+    }
+}
+
+impl UniversalHash for TablePolyval {
+    fn update_with_backend(&mut self, f: impl UhfClosure<BlockSize = Self::BlockSize>) {
+        f.call(self);
+    }
+
+    /// Get POLYVAL result (i.e. computed `S` field element)
+    fn finalize(self) -> Tag {
+        let mut block = Block::default();
+        block[..8].copy_from_slice(&self.y.low.to_be_bytes());
+        block[8..].copy_from_slice(&self.y.high.to_be_bytes());
+        block.reverse();
+        block
+    }
+}
+
+impl Reset for TablePolyval {
+    fn reset(&mut self) {
+        self.y = FieldElement::default();
+    }
+}
+
+impl TablePolyval {
+    /// Multiply `y` by `H` using the precomputed table, one nibble at a time.
+    fn mul_h(&mut self) {
+        let mut z = FieldElement::default();
+
+        for mut word in [self.y.high, self.y.low] {
+            for _ in 0..16 {
+                let msw = z.high & 0xf;
+                z.high >>= 4;
+                z.high |= z.low << 60;
+                z.low >>= 4;
+                z.low ^= u64::from(REDUCTION_TABLE[msw as usize]) << 48;
+
+                let t = &self.table[(word & 0xf) as usize];
+                z.low ^= t.low;
+                z.high ^= t.high;
+
+                word >>= 4;
+            }
+        }
+
+        self.y = z;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for TablePolyval {
+    fn drop(&mut self) {
+        for element in &mut self.table {
+            element.zeroize();
+        }
+
+        self.y.zeroize();
+    }
+}
+
+opaque_debug::implement!(TablePolyval);
+
+/// GHASH-domain field element, with the first 8 bytes (big endian) in `low`
+/// and the last 8 bytes (big endian) in `high`.
+#[derive(Copy, Clone, Default)]
+struct FieldElement {
+    low: u64,
+    high: u64,
+}
+
+impl FieldElement {
+    fn from_be_bytes(bytes: &Block) -> Self {
+        Self {
+            low: u64::from_be_bytes(bytes[..8].try_into().unwrap()),
+            high: u64::from_be_bytes(bytes[8..].try_into().unwrap()),
+        }
+    }
+
+    /// Add two field elements.
+    fn add(&self, rhs: &Self) -> Self {
+        Self {
+            low: self.low ^ rhs.low,
+            high: self.high ^ rhs.high,
+        }
+    }
+
+    /// Multiply by `x` (a.k.a. `mulX_GHASH`).
+    fn double(&self) -> Self {
+        let msb_set = self.high & 1 == 1;
+        let mut low = self.low >> 1;
+
+        if msb_set {
+            low ^= 0xe100_0000_0000_0000;
+        }
+
+        Self {
+            low,
+            high: (self.high >> 1) | (self.low << 63),
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for FieldElement {
+    fn zeroize(&mut self) {
+        self.low.zeroize();
+        self.high.zeroize();
+    }
+}
+
+/// Reverse the order of the low 4 bits of `i`.
+fn reverse_bits(i: usize) -> usize {
+    ((i << 3) & 0x8) | ((i << 1) & 0x4) | ((i >> 1) & 0x2) | ((i >> 3) & 0x1)
+}
@@ -0,0 +1,38 @@
+//! Diagnostics for checking which block processing path inputs take.
+
+/// Number of calls made to each of a universal hash backend's block
+/// processing methods.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProcStats {
+    /// Number of calls to `proc_par_blocks`, each processing
+    /// `par_blocks_size` blocks.
+    pub par_calls: usize,
+
+    /// Number of calls to `proc_block`.
+    pub single_calls: usize,
+
+    /// Number of blocks the backend processes in parallel.
+    pub par_blocks_size: usize,
+}
+
+impl ProcStats {
+    /// Create empty stats for a backend which processes `par_blocks_size`
+    /// blocks in parallel.
+    pub(crate) fn new(par_blocks_size: usize) -> Self {
+        Self {
+            par_blocks_size,
+            ..Default::default()
+        }
+    }
+
+    /// Get the calls made since the `earlier` stats were taken, e.g. ones
+    /// obtained from [`Polyval::proc_stats`][`crate::Polyval::proc_stats`]
+    /// before inputting some blocks.
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            par_calls: self.par_calls.wrapping_sub(earlier.par_calls),
+            single_calls: self.single_calls.wrapping_sub(earlier.single_calls),
+            par_blocks_size: self.par_blocks_size,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Object-safe adapter for universal hash functions.
+
+use crate::{Block, Tag};
+use alloc::boxed::Box;
+use universal_hash::{consts::U16, UniversalHash};
+
+/// Object-safe counterpart to [`UniversalHash`] for universal hash functions
+/// with a 16-byte block size.
+///
+/// [`UniversalHash::update_with_backend`] is generic over its closure
+/// argument, which means `dyn UniversalHash` can't be used. This trait is
+/// implemented for all such [`UniversalHash`] types (including `Polyval`,
+/// `GHash`, and `Poly1305`), allowing universal hash functions selected at
+/// runtime to be stored as `Box<dyn DynUniversalHash>`.
+///
+/// Methods are prefixed with `dyn_` so they don't conflict with the
+/// [`UniversalHash`] methods of the same types when both traits are in scope.
+pub trait DynUniversalHash {
+    /// Input the given blocks into the universal hash function.
+    fn dyn_update(&mut self, blocks: &[Block]);
+
+    /// Input data into the universal hash function, zero-padding any trailing
+    /// partial block.
+    fn dyn_update_padded(&mut self, data: &[u8]);
+
+    /// Retrieve the result of the universal hash function.
+    fn finalize_boxed(self: Box<Self>) -> Tag;
+}
+
+impl<U> DynUniversalHash for U
+where
+    U: UniversalHash<BlockSize = U16>,
+{
+    fn dyn_update(&mut self, blocks: &[Block]) {
+        UniversalHash::update(self, blocks);
+    }
+
+    fn dyn_update_padded(&mut self, data: &[u8]) {
+        UniversalHash::update_padded(self, data);
+    }
+
+    fn finalize_boxed(self: Box<Self>) -> Tag {
+        UniversalHash::finalize(*self)
+    }
+}
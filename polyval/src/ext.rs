@@ -0,0 +1,26 @@
+//! Convenience methods for universal hash functions.
+
+use universal_hash::{Block, Key, KeyInit, UniversalHash};
+
+/// Extension trait providing chaining and one-shot helpers for all
+/// [`UniversalHash`] implementations which can be initialized from a key
+/// (including `Polyval` and `GHash`).
+///
+/// Verification is provided by [`UniversalHash::verify`].
+pub trait UniversalHashExt: UniversalHash + KeyInit {
+    /// Input the given blocks, returning the updated state.
+    fn chain_update(mut self, blocks: &[Block<Self>]) -> Self {
+        self.update(blocks);
+        self
+    }
+
+    /// Compute the universal hash of the given data under the given key,
+    /// zero-padding any trailing partial block.
+    fn compute(key: &Key<Self>, data: &[u8]) -> Block<Self> {
+        let mut hash = Self::new(key);
+        hash.update_padded(data);
+        hash.finalize()
+    }
+}
+
+impl<U> UniversalHashExt for U where U: UniversalHash + KeyInit {}
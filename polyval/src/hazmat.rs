@@ -0,0 +1,92 @@
+//! Low-level utilities for working with POLYVAL blocks.
+//!
+//! POLYVAL field elements are encoded as little endian. These are the
+//! canonical conversions between blocks and `u128` values used throughout
+//! this crate, e.g. for the initial block passed to
+//! [`Polyval::new_with_init_block`][`crate::Polyval::new_with_init_block`].
+//!
+//! When the `hazmat` feature is enabled, `gf_mul`, `gf_mul_unreduced` and
+//! `gf_reduce` expose the field arithmetic of the selected backend (i.e.
+//! CLMUL/PMULL when available) for use by external AEAD implementations.
+
+use crate::Block;
+
+/// Convert a `u128` into a block, encoded as little endian.
+#[inline]
+pub fn block_from_u128_le(x: u128) -> Block {
+    x.to_le_bytes().into()
+}
+
+/// Convert a block into a `u128`, decoded as little endian.
+#[inline]
+pub fn block_to_u128_le(block: &Block) -> u128 {
+    u128::from_le_bytes((*block).into())
+}
+
+/// Multiply two POLYVAL field elements, i.e. compute
+/// `dot(a, b) = a * b * x^-128` as defined in [RFC 8452 Section 3].
+///
+/// This is a single step of POLYVAL: `POLYVAL(H, X_1) = dot(X_1, H)`. It uses
+/// the same backend as [`Polyval`][`crate::Polyval`], so it's accelerated by
+/// CLMUL/PMULL when available and falls back to the constant-time "soft"
+/// backend otherwise.
+///
+/// The operands use POLYVAL's little endian bit order. To multiply GHASH field
+/// elements `a` and `b`, compute
+/// `ByteReverse(gf_mul(ByteReverse(a), mulx(ByteReverse(b))))` (see
+/// [`mulx`][`crate::mulx`] and [RFC 8452 Appendix A]).
+///
+/// # ⚠️ Warning: [Hazmat!]
+///
+/// This is a raw field operation which provides none of the security
+/// guarantees of POLYVAL on its own.
+///
+/// [RFC 8452 Section 3]: https://tools.ietf.org/html/rfc8452#section-3
+/// [RFC 8452 Appendix A]: https://tools.ietf.org/html/rfc8452#appendix-A
+/// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+#[cfg(feature = "hazmat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+#[inline]
+pub fn gf_mul(a: &Block, b: &Block) -> Block {
+    gf_reduce(&gf_mul_unreduced(a, b))
+}
+
+/// Compute the 256-bit carryless product `a * b` of two POLYVAL field
+/// elements without reducing it, encoded as little endian (low half first).
+///
+/// Reduction is linear, so the products of several pairs of field elements
+/// can be added (XORed) together and reduced once with [`gf_reduce`], e.g.
+/// when processing several blocks in parallel using powers of `H`:
+///
+/// ```text
+/// gf_reduce(gf_mul_unreduced(a, b) ^ gf_mul_unreduced(c, d)) == gf_mul(a, b) ^ gf_mul(c, d)
+/// ```
+///
+/// # ⚠️ Warning: [Hazmat!]
+///
+/// This is a raw field operation which provides none of the security
+/// guarantees of POLYVAL on its own.
+///
+/// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+#[cfg(feature = "hazmat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+#[inline]
+pub fn gf_mul_unreduced(a: &Block, b: &Block) -> [Block; 2] {
+    crate::backend::mul_unreduced(a, b)
+}
+
+/// Reduce a 256-bit carryless product computed with [`gf_mul_unreduced`] to a
+/// POLYVAL field element, i.e. compute `product * x^-128`.
+///
+/// # ⚠️ Warning: [Hazmat!]
+///
+/// This is a raw field operation which provides none of the security
+/// guarantees of POLYVAL on its own.
+///
+/// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+#[cfg(feature = "hazmat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+#[inline]
+pub fn gf_reduce(product: &[Block; 2]) -> Block {
+    crate::backend::reduce(product)
+}
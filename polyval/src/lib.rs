@@ -27,6 +27,43 @@
 //! compact implementation which uses a clever but simple technique to avoid
 //! carry-spilling.
 //!
+//! This backend is always used when running under [Miri]. It's also available
+//! as [`SoftPolyval`] regardless of platform, e.g. for debugging divergences
+//! between backends.
+//!
+//! Only the intrinsics backend for the target architecture is compiled. To
+//! exclude it as well (e.g. for fixed-target builds), set the
+//! `polyval_force_soft` cfg, which selects this backend unconditionally:
+//!
+//! ```text
+//! $ RUSTFLAGS="--cfg polyval_force_soft" cargo build
+//! ```
+//!
+//! This is a cfg rather than a Cargo feature as features are unified across
+//! the dependency graph, so any crate enabling it would disable the
+//! intrinsics backends for every other user of this crate in the same build.
+//!
+//! The "soft" backend has 32-bit and 64-bit implementations. The 64-bit one
+//! is used on 64-bit targets, and also on WebAssembly (`wasm32`), where 64-bit
+//! multiplication is native and it's roughly twice as fast. Other 32-bit
+//! targets use the 32-bit implementation, as 64-bit multiplication may not be
+//! constant time on them (e.g. Cortex-M3). For testing, the 32-bit
+//! implementation can be selected on any target with the `polyval_soft32`
+//! cfg:
+//!
+//! ```text
+//! $ RUSTFLAGS="--cfg polyval_force_soft --cfg polyval_soft32" cargo test
+//! ```
+//!
+//! ## "table-soft" portable backend (not constant time!)
+//! The opt-in `table-soft` feature provides [`TablePolyval`], a portable
+//! implementation using Shoup's 4-bit precomputed table method, which can be
+//! faster than the "soft" backend on platforms lacking a carryless multiply.
+//!
+//! ⚠️ It is **not** constant time: it performs lookups into a key-dependent
+//! table using secret indices, which leaks the key via cache timing side
+//! channels. Only use it if you've deliberately accepted this tradeoff.
+//!
 //! ## ARMv8 intrinsics (`PMULL`, MSRV 1.61+)
 //! On `aarch64` targets including `aarch64-apple-darwin` (Apple M1) and Linux
 //! targets such as `aarch64-unknown-linux-gnu` and `aarch64-unknown-linux-musl`,
@@ -35,13 +72,16 @@
 //! On Linux and macOS, support for `PMULL` intrinsics is autodetected at runtime.
 //! On other platforms the `crypto` target feature must be enabled via RUSTFLAGS.
 //!
+//! Big endian AArch64 targets (`aarch64_be`) always use the "soft" backend.
+//!
 //! ## `x86`/`x86_64` intrinsics (`CMLMUL`)
 //! By default this crate uses runtime detection on `i686`/`x86_64` targets
 //! in order to determine if `CLMUL` is available, and if it is not, it will
 //! fallback to using a constant-time software implementation.
 //!
 //! For optimal performance, set `target-cpu` in `RUSTFLAGS` to `sandybridge`
-//! or newer:
+//! or newer, which statically enables `CLMUL` so it's used directly without
+//! runtime detection:
 //!
 //! Example:
 //!
@@ -67,6 +107,7 @@
 //! [AES-GCM-SIV]: https://en.wikipedia.org/wiki/AES-GCM-SIV
 //! [AES-GCM/GMAC]: https://en.wikipedia.org/wiki/Galois/Counter_Mode
 //! [BearSSL]: https://www.bearssl.org/constanttime.html#ghash-for-gcm
+//! [Miri]: https://github.com/rust-lang/miri
 //! [RFC 8452 Section 3]: https://tools.ietf.org/html/rfc8452#section-3
 //! [RFC 8452 Appendix A]: https://tools.ietf.org/html/rfc8452#appendix-A
 
@@ -78,13 +119,62 @@
 )]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(any(feature = "alloc", feature = "proptest"))]
+extern crate alloc;
+
+mod auth_key;
 mod backend;
+#[cfg(feature = "alloc")]
+mod dynamic;
+mod ext;
 mod mulx;
+#[cfg(feature = "serde")]
+mod serialization;
+mod streaming;
+mod tag_hex;
+
+pub mod hazmat;
+pub mod siv;
+
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+pub mod diagnostics;
+
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub mod testing;
+
+pub use crate::{
+    auth_key::AuthKey,
+    backend::{detected_capabilities, Capabilities, Polyval, SoftPolyval},
+    ext::UniversalHashExt,
+    mulx::mulx,
+    streaming::StreamingMac,
+    tag_hex::TagHex,
+};
+pub use universal_hash::{
+    self,
+    crypto_common::{BlockSizeUser, KeySizeUser},
+    KeyInit, Reset, UniversalHash,
+};
+
+use universal_hash::{consts, UhfBackend, UhfClosure};
 
-pub use crate::{backend::Polyval, mulx::mulx};
-pub use universal_hash;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::dynamic::DynUniversalHash;
 
-opaque_debug::implement!(Polyval);
+#[cfg(feature = "secrecy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secrecy")))]
+pub use secrecy;
+
+#[cfg(feature = "hex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+pub use base16ct;
+
+#[cfg(feature = "table-soft")]
+#[cfg_attr(docsrs, doc(cfg(feature = "table-soft")))]
+pub use crate::backend::TablePolyval;
 
 /// Size of a POLYVAL block in bytes
 pub const BLOCK_SIZE: usize = 16;
@@ -100,3 +190,506 @@ pub type Block = universal_hash::Block<Polyval>;
 
 /// POLYVAL tags (16-bytes)
 pub type Tag = universal_hash::Block<Polyval>;
+
+/// Byte order of a tag output by [`Polyval::finalize_with_order`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ByteOrder {
+    /// POLYVAL's native little endian byte order.
+    Polyval,
+
+    /// GHASH's big endian byte order, i.e. the POLYVAL output byte-reversed.
+    Ghash,
+}
+
+/// Zeroize the given POLYVAL [`Key`] in place.
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub fn zeroize_key(key: &mut Key) {
+    use zeroize::Zeroize;
+    key.as_mut_slice().zeroize();
+}
+
+impl Polyval {
+    /// Initialize POLYVAL with the given [`AuthKey`].
+    pub fn new_from_authkey(key: &AuthKey) -> Self {
+        Self::new(key.as_key())
+    }
+
+    /// Initialize POLYVAL with the given `H` field element as a byte array.
+    pub fn new_from_array(h: &[u8; KEY_SIZE]) -> Self {
+        Self::new(h.into())
+    }
+
+    /// Initialize POLYVAL with the given secret key.
+    #[cfg(feature = "secrecy")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secrecy")))]
+    pub fn new_from_secret(key: &secrecy::SecretBox<Key>) -> Self {
+        use secrecy::ExposeSecret;
+        Self::new(key.expose_secret())
+    }
+
+    /// Initialize POLYVAL with a key given as a hex string (upper or lower
+    /// case), e.g. one embedded in firmware as a string literal.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    pub fn from_hex_key(s: &str) -> Result<Self, base16ct::Error> {
+        let mut key = Key::default();
+        let result = match base16ct::mixed::decode(s, &mut key).map(|decoded| decoded.len()) {
+            Ok(len) if len == key.len() => Ok(Self::new(&key)),
+            Ok(_) => Err(base16ct::Error::InvalidLength),
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            use zeroize::Zeroize;
+            key.as_mut_slice().zeroize();
+        }
+
+        result
+    }
+
+    /// Get the number of bytes absorbed so far including any padding (e.g.
+    /// added by [`UniversalHash::update_padded`]), saturating at [`u64::MAX`].
+    ///
+    /// As input is absorbed in whole blocks, this is always a multiple of
+    /// [`BLOCK_SIZE`] and may exceed the length of the unpadded input.
+    pub fn padded_len(&self) -> u64 {
+        self.block_count().saturating_mul(BLOCK_SIZE as u64)
+    }
+
+    /// Has nothing been absorbed since this instance was initialized or reset?
+    pub fn is_empty(&self) -> bool {
+        self.block_count() == 0
+    }
+
+    /// Clone this instance with the same key, discarding all input processed
+    /// so far, i.e. the clone starts again from the initial block.
+    pub fn clone_reset(&self) -> Self {
+        let mut polyval = self.clone();
+        polyval.reset();
+        polyval
+    }
+
+    /// Get the POLYVAL tag for an empty input under the given key without
+    /// constructing an instance.
+    ///
+    /// This is always the all-zero block, as the accumulator starts at zero.
+    #[must_use]
+    pub fn empty_tag(_h: &Key) -> Tag {
+        Tag::default()
+    }
+
+    /// Get POLYVAL result XORed with the given mask, e.g. an encrypted
+    /// counter block.
+    #[must_use]
+    pub fn finalize_xor(self, mask: &Block) -> Tag {
+        let mut tag = self.finalize();
+        for (a, b) in tag.iter_mut().zip(mask.iter()) {
+            *a ^= b;
+        }
+        tag
+    }
+
+    /// Get POLYVAL result over exactly the input so far.
+    ///
+    /// This is the same as [`UniversalHash::finalize`]: neither appends a
+    /// block encoding the input lengths, so constructions which require one
+    /// must input it themselves, or use [`Polyval::finalize_siv`]:
+    ///
+    /// ```
+    /// use polyval::{universal_hash::{KeyInit, UniversalHash}, Polyval};
+    ///
+    /// let (aad, pt) = (b"header", b"message");
+    ///
+    /// let mut polyval = Polyval::new(&[0x42; 16].into());
+    /// polyval.update_padded(aad);
+    /// polyval.update_padded(pt);
+    /// let tag = polyval.clone().finalize_siv(aad.len(), pt.len());
+    ///
+    /// // Input the AES-GCM-SIV lengths block manually
+    /// polyval.update_u128(((pt.len() as u128 * 8) << 64) | (aad.len() as u128 * 8));
+    /// assert_eq!(tag, polyval.finalize_raw());
+    /// ```
+    #[must_use]
+    pub fn finalize_raw(self) -> Tag {
+        UniversalHash::finalize(self)
+    }
+
+    /// Input the AES-GCM-SIV lengths block for additional data and plaintext
+    /// of the given lengths in bytes, then get POLYVAL result.
+    ///
+    /// The block contains the lengths in bits as little endian 64-bit
+    /// integers, as described in [RFC 8452 Section 4] (see
+    /// [`siv::polyval_siv`]).
+    ///
+    /// [RFC 8452 Section 4]: https://tools.ietf.org/html/rfc8452#section-4
+    #[must_use]
+    pub fn finalize_siv(mut self, aad_len: usize, pt_len: usize) -> Tag {
+        let aad_bits = aad_len as u128 * 8;
+        let pt_bits = pt_len as u128 * 8;
+        self.update_u128((pt_bits << 64) | aad_bits);
+        self.finalize_raw()
+    }
+
+    /// Get POLYVAL result in the given byte order.
+    ///
+    /// [`ByteOrder::Ghash`] reverses the output, which is the final step of
+    /// computing GHASH with POLYVAL (see [`Polyval::update_reversed`]).
+    #[must_use]
+    pub fn finalize_with_order(self, order: ByteOrder) -> Tag {
+        let mut tag = self.finalize();
+        if order == ByteOrder::Ghash {
+            tag.reverse();
+        }
+        tag
+    }
+
+    /// Get POLYVAL result wrapped so that it's zeroized when dropped.
+    #[cfg(feature = "zeroize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+    #[must_use]
+    pub fn finalize_zeroizing(self) -> zeroize::Zeroizing<Tag> {
+        zeroize::Zeroizing::new(self.finalize())
+    }
+
+    /// Write the leading `min(out.len(), BLOCK_SIZE)` bytes of POLYVAL result to `out`,
+    /// i.e. a truncated tag.
+    ///
+    /// # ⚠️ Warning: truncation reduces forgery resistance
+    ///
+    /// A tag truncated to `t` bits can be forged by guessing with probability
+    /// at least `2^-t` per attempt. Worse, with GHASH-style MACs each
+    /// successful forgery reveals information about the key which makes
+    /// subsequent forgeries easier (see [NIST SP 800-38D] Appendix C), so
+    /// short tags require strict limits on the number of failed verifications
+    /// and the message length.
+    ///
+    /// [NIST SP 800-38D]: https://csrc.nist.gov/pubs/sp/800/38/d/final
+    pub fn finalize_truncated(self, out: &mut [u8]) {
+        let tag = self.finalize();
+        let len = out.len().min(tag.len());
+        out[..len].copy_from_slice(&tag[..len]);
+    }
+
+    /// Get POLYVAL result as a byte array.
+    #[must_use]
+    pub fn finalize_bytes(self) -> [u8; BLOCK_SIZE] {
+        self.finalize().into()
+    }
+
+    /// Get POLYVAL result along with the raw accumulator bytes, which can be
+    /// restored into an instance with the same key using `set_accumulator`.
+    ///
+    /// For POLYVAL the accumulator is the tag itself.
+    ///
+    /// # ⚠️ Warning: [Hazmat!]
+    ///
+    /// The accumulator of an unfinished computation reveals information
+    /// about the key, so it must be kept confidential.
+    ///
+    /// [Hazmat!]: https://github.com/RustCrypto/meta/blob/master/HAZMAT.md
+    #[cfg(feature = "hazmat")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
+    #[must_use]
+    pub fn finalize_with_state(self) -> (Tag, [u8; BLOCK_SIZE]) {
+        let tag = self.finalize();
+        (tag, tag.into())
+    }
+
+    /// Compute POLYVAL over exactly the given blocks (without padding) using
+    /// the given `H` field element.
+    #[must_use]
+    pub fn hash_blocks(h: &Key, blocks: &[Block]) -> Tag {
+        let mut polyval = Self::new(h);
+        polyval.update(blocks);
+        polyval.finalize()
+    }
+
+    /// Compute the tag over each successive prefix of the concatenation of
+    /// `chunks`, i.e. the tag after absorbing each chunk cumulatively.
+    ///
+    /// Each tag is the same as computing POLYVAL with
+    /// [`UniversalHash::update_padded`] over all the chunks up to and
+    /// including that one. Chunks need not be a multiple of the block size.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn compute_chain(h: &Key, chunks: &[&[u8]]) -> alloc::vec::Vec<Tag> {
+        let mut mac = StreamingMac::<Self>::new(h);
+
+        chunks
+            .iter()
+            .map(|chunk| {
+                mac.push(chunk);
+                mac.snapshot()
+            })
+            .collect()
+    }
+
+    /// Input the given blocks, reporting how they were dispatched to the
+    /// backend's block processing methods.
+    #[cfg(feature = "diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+    pub fn update_counted(&mut self, blocks: &[Block]) -> diagnostics::ProcStats {
+        let start = self.proc_stats();
+        self.update(blocks);
+        self.proc_stats().since(start)
+    }
+
+    /// Input a single block.
+    ///
+    /// Equivalent to `update(&[*block])`, but passes the block directly to
+    /// the backend.
+    pub fn update_block(&mut self, block: &Block) {
+        self.update_with_backend(SingleBlock(block));
+    }
+
+    /// Input a single block given as a `u128`, encoded as little endian.
+    pub fn update_u128(&mut self, block: u128) {
+        self.update_block(&hazmat::block_from_u128_le(block));
+    }
+
+    /// Input data (zero-padding any trailing partial block, like
+    /// [`UniversalHash::update_padded`]) while reporting progress.
+    ///
+    /// The data is processed in chunks of 64 KiB, and after each chunk
+    /// `cb` is invoked with the total number of bytes processed so far. The
+    /// last invocation is always with `data.len()`, including when `data` is
+    /// empty.
+    pub fn update_with_progress(&mut self, data: &[u8], mut cb: impl FnMut(usize)) {
+        /// Number of blocks processed between progress callbacks (64 KiB)
+        const PROGRESS_CHUNK_BLOCKS: usize = 4096;
+
+        let (blocks, tail) = Block::slice_as_chunks(data);
+        let mut processed = 0;
+
+        for chunk in blocks.chunks(PROGRESS_CHUNK_BLOCKS) {
+            self.update(chunk);
+            processed += chunk.len() * BLOCK_SIZE;
+            cb(processed);
+        }
+
+        if !tail.is_empty() || data.is_empty() {
+            self.update_padded(tail);
+            cb(data.len());
+        }
+    }
+
+    /// Input the given data prefixed with its length.
+    ///
+    /// The length is encoded as a block whose first 8 bytes contain the length
+    /// of `data` in bytes as a big endian `u64`, with the remaining 8 bytes
+    /// set to zero. It's followed by `data`, zero-padded to a multiple of the
+    /// block size.
+    pub fn update_length_prefixed(&mut self, data: &[u8]) {
+        let mut prefix = Block::default();
+        prefix[..8].copy_from_slice(&(data.len() as u64).to_be_bytes());
+        self.update_block(&prefix);
+        self.update_padded(data);
+    }
+
+    /// Input the given blocks, byte-reversing each one before absorbing it.
+    ///
+    /// This is the input transformation GHASH applies on top of POLYVAL, so
+    /// provided the key was converted with [`mulx`] and the output tag is
+    /// also byte-reversed, the result is the GHASH of the given blocks.
+    pub fn update_reversed(&mut self, blocks: &[Block]) {
+        for block in blocks {
+            let mut block = *block;
+            block.reverse();
+            self.update_block(&block);
+        }
+    }
+
+    /// Input blocks given as byte arrays, without converting each one to a
+    /// [`Block`].
+    pub fn update_raw_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        self.update(Block::cast_slice_from_core(blocks));
+    }
+
+    /// Input blocks from an iterator, e.g. ones generated lazily, without
+    /// collecting them into a slice first.
+    ///
+    /// Blocks are buffered on the stack and passed to the backend in batches.
+    pub fn update_iter(&mut self, blocks: impl Iterator<Item = Block>) {
+        /// Number of blocks buffered per call to the backend
+        const ITER_BATCH_BLOCKS: usize = 8;
+
+        let mut buffer = [Block::default(); ITER_BATCH_BLOCKS];
+        let mut len = 0;
+
+        for block in blocks {
+            buffer[len] = block;
+            len += 1;
+
+            if len == buffer.len() {
+                self.update(&buffer);
+                len = 0;
+            }
+        }
+
+        self.update(&buffer[..len]);
+    }
+
+    /// Input `count` blocks located every `stride` bytes within `base`, i.e.
+    /// the block `i` is `base[i * stride..][..16]`, without copying them into
+    /// a contiguous buffer first.
+    ///
+    /// # Panics
+    ///
+    /// If `stride` is less than the block size or `base` is too short to
+    /// contain `count` blocks.
+    pub fn update_strided(&mut self, base: &[u8], stride: usize, count: usize) {
+        assert!(
+            stride >= BLOCK_SIZE,
+            "stride must be at least the block size"
+        );
+
+        for i in 0..count {
+            let block = <&Block>::try_from(&base[i * stride..][..BLOCK_SIZE]).unwrap();
+            self.update_block(block);
+        }
+    }
+
+    /// Verify a buffer consisting of data followed by its 16-byte tag,
+    /// returning the authenticated data on success.
+    ///
+    /// The data is zero-padded (as with [`UniversalHash::update_padded`])
+    /// and the tag is compared in constant time. Buffers shorter than a tag
+    /// are rejected.
+    pub fn verify_streamed(mut self, data_then_tag: &[u8]) -> Result<&[u8], universal_hash::Error> {
+        let split = data_then_tag
+            .len()
+            .checked_sub(BLOCK_SIZE)
+            .ok_or(universal_hash::Error)?;
+        let (data, tag) = data_then_tag.split_at(split);
+
+        self.update_padded(data);
+        self.verify(&Tag::try_from(tag).map_err(|_| universal_hash::Error)?)?;
+        Ok(data)
+    }
+
+    /// Verify a batch of independent messages against their tags under this
+    /// instance's key, returning the indices of any which fail.
+    ///
+    /// Each message is zero-padded (as with [`UniversalHash::update_padded`])
+    /// and hashed starting from the initial block, i.e. any input already
+    /// processed by `self` is ignored. Tags are compared in constant time.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn verify_batch(&self, items: &[(&[u8], &Tag)]) -> Result<(), alloc::vec::Vec<usize>> {
+        let failures: alloc::vec::Vec<usize> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (data, tag))| {
+                let mut polyval = self.clone_reset();
+                polyval.update_padded(data);
+                polyval.verify(tag).err().map(|_| i)
+            })
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Input the first `len` bytes of `buf`, zero-padding any trailing partial
+    /// block in place within `buf` rather than copying it.
+    ///
+    /// Equivalent to `update_padded(&buf[..len])`, except the bytes of `buf`
+    /// following the data up to the next multiple of the block size are
+    /// overwritten with zeroes.
+    ///
+    /// # Panics
+    ///
+    /// If `buf` is too short to contain `len` bytes rounded up to a multiple
+    /// of the block size.
+    pub fn update_padded_in(&mut self, buf: &mut [u8], len: usize) {
+        let padded_len = len.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+        let buf = &mut buf[..padded_len];
+        buf[len..].fill(0);
+
+        let (blocks, _) = Block::slice_as_chunks(buf);
+        self.update(blocks);
+    }
+
+    /// Input data, padding any trailing partial block with `pad_byte`
+    /// followed by zeroes.
+    ///
+    /// If `set_length_bit` is `true`, a padding block is appended even when
+    /// the data is a multiple of the block size, which makes the padding
+    /// unambiguous, e.g. `update_padded_with(data, 0x80, true)` implements
+    /// `10*` padding.
+    ///
+    /// `update_padded_with(data, 0, false)` is equivalent to
+    /// [`UniversalHash::update_padded`]. Any other combination of arguments
+    /// is **not** compatible with POLYVAL as used by AES-GCM-SIV (RFC 8452).
+    pub fn update_padded_with(&mut self, data: &[u8], pad_byte: u8, set_length_bit: bool) {
+        let (blocks, tail) = Block::slice_as_chunks(data);
+        self.update(blocks);
+
+        if !tail.is_empty() || set_length_bit {
+            let mut padded = Block::default();
+            padded[..tail.len()].copy_from_slice(tail);
+            padded[tail.len()] = pad_byte;
+            self.update_block(&padded);
+        }
+    }
+
+    /// Input a final partial block, zero-extended to the block size, then get
+    /// POLYVAL result.
+    ///
+    /// This makes explicit that only the last block of a message may be
+    /// partial: as it consumes `self`, nothing can be input afterwards. The
+    /// result is the same as calling [`UniversalHash::update_padded`] with
+    /// `data` and then finalizing.
+    ///
+    /// # Panics
+    ///
+    /// If `data` is not shorter than the block size.
+    #[must_use]
+    pub fn finalize_partial(mut self, data: &[u8]) -> Tag {
+        assert!(
+            data.len() < BLOCK_SIZE,
+            "final partial block must be shorter than a block"
+        );
+        self.update_padded(data);
+        self.finalize()
+    }
+
+    /// Input data (zero-padding any trailing partial block, like
+    /// [`UniversalHash::update_padded`]), returning the updated state.
+    ///
+    /// This allows the tag to be computed in a single expression:
+    ///
+    /// ```
+    /// use polyval::{universal_hash::{KeyInit, UniversalHash}, Polyval};
+    ///
+    /// let h = [0x42; 16];
+    /// let tag = Polyval::new(&h.into()).absorb_padded(b"hello world").finalize();
+    ///
+    /// let mut polyval = Polyval::new(&h.into());
+    /// polyval.update_padded(b"hello world");
+    /// assert_eq!(tag, polyval.finalize());
+    /// ```
+    pub fn absorb_padded(mut self, data: &[u8]) -> Self {
+        self.update_padded(data);
+        self
+    }
+}
+
+/// Closure which inputs a single block into a backend.
+struct SingleBlock<'a>(&'a Block);
+
+impl BlockSizeUser for SingleBlock<'_> {
+    type BlockSize = consts::U16;
+}
+
+impl UhfClosure for SingleBlock<'_> {
+    fn call<B: UhfBackend<BlockSize = Self::BlockSize>>(self, backend: &mut B) {
+        backend.proc_block(self.0);
+    }
+}
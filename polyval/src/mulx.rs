@@ -7,6 +7,12 @@ use crate::Block;
 /// Performs a doubling (a.k.a. "multiply by x") over GF(2^128).
 /// This is useful for implementing GHASH in terms of POLYVAL.
 ///
+/// # Timing
+///
+/// This function is branch-free and executes in constant time: the reduction
+/// is applied by XORing in a mask derived from the high bit of the input, so
+/// it's safe to use on secret values such as the GHASH key `H`.
+///
 /// [1]: https://tools.ietf.org/html/rfc8452#appendix-A
 pub fn mulx(block: &Block) -> Block {
     let mut v = u128::from_le_bytes((*block).into());
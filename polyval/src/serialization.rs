@@ -0,0 +1,37 @@
+//! `serde` support.
+//!
+//! Instances are serialized as a tuple of the `H` key bytes, the initial
+//! block, the accumulator (each as 16-byte arrays) and the number of blocks
+//! processed. As this includes the key, serialized instances are as
+//! sensitive as the key itself.
+
+use crate::{Block, Key, Polyval};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// # ⚠️ Warning: contains the key!
+///
+/// The serialized form includes the raw `H` key bytes, so it must be
+/// protected (e.g. encrypted at rest) in the same way as the key itself.
+impl Serialize for Polyval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (h, init_block, acc, block_count) = self.to_parts();
+        let h: [u8; 16] = h.into();
+        let init_block: [u8; 16] = init_block.into();
+        let acc: [u8; 16] = acc.into();
+        (h, init_block, acc, block_count).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Polyval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (h, init_block, acc, block_count) =
+            <([u8; 16], [u8; 16], [u8; 16], u64)>::deserialize(deserializer)?;
+
+        Ok(Self::from_parts(&(
+            Key::from(h),
+            Block::from(init_block),
+            Block::from(acc),
+            block_count,
+        )))
+    }
+}
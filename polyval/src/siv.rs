@@ -0,0 +1,78 @@
+//! POLYVAL as used by AES-GCM-SIV.
+
+use crate::{Block, Key, Polyval, Tag};
+use universal_hash::{KeyInit, UniversalHash};
+
+/// Compute POLYVAL over the given additional data and plaintext as described
+/// in [RFC 8452 Section 4], using the given message-authentication key.
+///
+/// The input to POLYVAL is the additional data and plaintext, each
+/// zero-padded to a multiple of the block size, followed by a block
+/// containing their lengths in bits as little endian 64-bit integers.
+///
+/// Note that the result is the raw POLYVAL output `S_s`: the AES-GCM-SIV tag
+/// is derived from it by XORing in the nonce, clearing the most significant
+/// bit of the last byte, and encrypting it with the message-encryption key.
+///
+/// [RFC 8452 Section 4]: https://tools.ietf.org/html/rfc8452#section-4
+#[must_use]
+pub fn polyval_siv(auth_key: &Key, aad: &[u8], pt: &[u8]) -> Tag {
+    let mut polyval = Polyval::new(auth_key);
+    polyval.update_padded(aad);
+    polyval.update_padded(pt);
+    polyval.finalize_siv(aad.len(), pt.len())
+}
+
+/// Derive the per-nonce message-authentication key and message-encryption key
+/// from the key-generating key as described in [RFC 8452 Section 4].
+///
+/// `encrypt_block` must encrypt a single block in-place under the
+/// key-generating key using AES-128 or AES-256, e.g. by calling
+/// `BlockCipherEncrypt::encrypt_block` on an AES instance. `N` is the size of
+/// the message-encryption key: 16 for AES-128 or 32 for AES-256. Any other
+/// size fails to compile:
+///
+/// ```compile_fail
+/// let (auth_key, enc_key) = polyval::siv::derive_keys::<24>(|_| (), &[0; 12]);
+/// ```
+///
+/// The keys are the concatenation of the first 8 bytes of the encryptions of
+/// the blocks `LE32(i) || nonce` for `i = 0, 1, ...`, i.e. the
+/// message-authentication key is derived from the first two blocks.
+///
+/// [RFC 8452 Section 4]: https://tools.ietf.org/html/rfc8452#section-4
+#[must_use]
+pub fn derive_keys<const N: usize>(
+    mut encrypt_block: impl FnMut(&mut Block),
+    nonce: &[u8; 12],
+) -> (Key, [u8; N]) {
+    const {
+        assert!(
+            N == 16 || N == 32,
+            "message-encryption key must be 16 or 32 bytes"
+        );
+    }
+
+    let mut auth_key = Key::default();
+    let mut enc_key = [0u8; N];
+    let mut block = Block::default();
+
+    for (i, chunk) in auth_key
+        .chunks_mut(8)
+        .chain(enc_key.chunks_mut(8))
+        .enumerate()
+    {
+        block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+        block[4..].copy_from_slice(nonce);
+        encrypt_block(&mut block);
+        chunk.copy_from_slice(&block[..8]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    {
+        use zeroize::Zeroize;
+        block.as_mut_slice().zeroize();
+    }
+
+    (auth_key, enc_key)
+}
@@ -0,0 +1,100 @@
+//! Streaming wrapper which supports computing intermediate tags.
+
+use crate::Polyval;
+use core::fmt;
+use universal_hash::{Block, Key, KeyInit, Reset, UniversalHash};
+
+/// Streaming universal hash which accepts input of arbitrary length and can
+/// emit intermediate tags over the data input so far.
+///
+/// Only a single block of buffered input is retained in addition to the
+/// underlying universal hash state, so memory usage is bounded regardless of
+/// how much data is input.
+///
+/// Tags are computed over the input zero-padded to a multiple of the block
+/// size, i.e. they are the same as would be computed by calling
+/// [`UniversalHash::update_padded`] on all of the data input so far.
+#[derive(Clone)]
+pub struct StreamingMac<U = Polyval>
+where
+    U: UniversalHash,
+{
+    inner: U,
+    buffer: Block<U>,
+    pos: usize,
+}
+
+impl<U> StreamingMac<U>
+where
+    U: UniversalHash + Clone,
+{
+    /// Initialize a [`StreamingMac`] with the given key.
+    pub fn new(key: &Key<U>) -> Self
+    where
+        U: KeyInit,
+    {
+        U::new(key).into()
+    }
+
+    /// Input data into the universal hash function.
+    pub fn push(&mut self, mut data: &[u8]) {
+        if self.pos > 0 {
+            let n = core::cmp::min(self.buffer.len() - self.pos, data.len());
+            self.buffer[self.pos..][..n].copy_from_slice(&data[..n]);
+            self.pos += n;
+            data = &data[n..];
+
+            if self.pos < self.buffer.len() {
+                return;
+            }
+
+            self.inner.update(core::slice::from_ref(&self.buffer));
+            self.pos = 0;
+        }
+
+        let (blocks, tail) = Block::<U>::slice_as_chunks(data);
+        self.inner.update(blocks);
+        self.buffer[..tail.len()].copy_from_slice(tail);
+        self.pos = tail.len();
+    }
+
+    /// Compute a tag over the data input so far without consuming `self`.
+    #[must_use]
+    pub fn snapshot(&self) -> Block<U> {
+        let mut inner = self.inner.clone();
+        inner.update_padded(&self.buffer[..self.pos]);
+        inner.finalize()
+    }
+
+    /// Discard all input so far, retaining the key.
+    pub fn reset_keep_key(&mut self)
+    where
+        U: Reset,
+    {
+        self.inner.reset();
+        self.buffer = Block::<U>::default();
+        self.pos = 0;
+    }
+}
+
+impl<U> From<U> for StreamingMac<U>
+where
+    U: UniversalHash,
+{
+    fn from(inner: U) -> Self {
+        Self {
+            inner,
+            buffer: Block::<U>::default(),
+            pos: 0,
+        }
+    }
+}
+
+impl<U> fmt::Debug for StreamingMac<U>
+where
+    U: UniversalHash,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StreamingMac { ... }")
+    }
+}
@@ -0,0 +1,30 @@
+//! Hex formatting for tags.
+
+use crate::Tag;
+use core::fmt;
+
+/// Wrapper for formatting a [`Tag`] as hex without allocating, e.g. for
+/// logging in `no_std` environments.
+///
+/// [`fmt::Display`] and [`fmt::LowerHex`] output lower case hex, while
+/// [`fmt::UpperHex`] outputs upper case hex.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TagHex<'a>(pub &'a Tag);
+
+impl fmt::Display for TagHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for TagHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl fmt::UpperHex for TagHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+    }
+}
@@ -0,0 +1,50 @@
+//! [`proptest`](mod@proptest) strategies for generating POLYVAL inputs.
+//!
+//! These are intended for use by downstream crates which want to
+//! property-test constructions built on POLYVAL.
+//!
+//! # Example
+//!
+//! ```
+//! use polyval::{
+//!     testing::{arb_blocks, arb_key},
+//!     universal_hash::{KeyInit, UniversalHash},
+//!     Polyval,
+//! };
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn update_is_incremental(key in arb_key(), blocks in arb_blocks(0..64)) {
+//!         let mut a = Polyval::new(&key);
+//!         a.update(&blocks);
+//!
+//!         let mut b = Polyval::new(&key);
+//!         for block in &blocks {
+//!             b.update(core::slice::from_ref(block));
+//!         }
+//!
+//!         prop_assert_eq!(a.finalize(), b.finalize());
+//!     }
+//! }
+//! # update_is_incremental();
+//! ```
+
+use crate::{Block, Key, BLOCK_SIZE, KEY_SIZE};
+use alloc::vec::Vec;
+use proptest::{collection::SizeRange, prelude::*};
+
+/// Strategy which generates arbitrary POLYVAL keys.
+pub fn arb_key() -> impl Strategy<Value = Key> {
+    any::<[u8; KEY_SIZE]>().prop_map(Key::from)
+}
+
+/// Strategy which generates arbitrary POLYVAL blocks.
+pub fn arb_block() -> impl Strategy<Value = Block> {
+    any::<[u8; BLOCK_SIZE]>().prop_map(Block::from)
+}
+
+/// Strategy which generates sequences of arbitrary POLYVAL blocks whose
+/// length is within the given range.
+pub fn arb_blocks(len: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Block>> {
+    proptest::collection::vec(arb_block(), len)
+}
@@ -1,7 +1,7 @@
 use hex_literal::hex;
 use polyval::{
-    universal_hash::{KeyInit, UniversalHash},
-    Polyval, BLOCK_SIZE,
+    AuthKey, BlockSizeUser, ByteOrder, KeyInit, KeySizeUser, Polyval, Reset, SoftPolyval,
+    StreamingMac, UniversalHash, BLOCK_SIZE,
 };
 
 //
@@ -16,6 +16,24 @@ const X_2: [u8; BLOCK_SIZE] = hex!("d1a24ddd2721d006bbe45f20d3c9f362");
 /// POLYVAL(H, X_1, X_2)
 const POLYVAL_RESULT: [u8; BLOCK_SIZE] = hex!("f7a3b47b846119fae5b7866cf5e5b77e");
 
+/// Generate `n` deterministic pseudorandom blocks from `seed` using xorshift64.
+fn xorshift_blocks(seed: u64, n: usize) -> impl Iterator<Item = polyval::Block> {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..n).map(move |_| {
+        let mut block = polyval::Block::default();
+        block[..8].copy_from_slice(&next().to_le_bytes());
+        block[8..].copy_from_slice(&next().to_le_bytes());
+        block
+    })
+}
+
 #[test]
 fn polyval_test_vector() {
     let mut poly = Polyval::new(&H.into());
@@ -24,3 +42,1123 @@ fn polyval_test_vector() {
     let result = poly.finalize();
     assert_eq!(&POLYVAL_RESULT[..], result.as_slice());
 }
+
+/// Miri selects the portable backend, so the RFC 8452 vectors can be checked
+/// for undefined behavior under Miri.
+#[cfg(miri)]
+#[test]
+fn miri_selects_soft_backend() {
+    assert_eq!(polyval::detected_capabilities(), Default::default());
+    polyval_test_vector();
+}
+
+/// The `polyval_force_soft` cfg excludes the intrinsics backends entirely.
+#[cfg(polyval_force_soft)]
+#[test]
+fn force_soft_excludes_intrinsics() {
+    assert_eq!(polyval::detected_capabilities(), Default::default());
+    assert_eq!(
+        core::any::TypeId::of::<Polyval>(),
+        core::any::TypeId::of::<SoftPolyval>()
+    );
+    polyval_test_vector();
+}
+
+/// RFC 8452 test vector using whichever of the 32-bit or 64-bit "soft"
+/// backend implementations is selected for the target (see `polyval_soft32`)
+#[test]
+fn soft_rfc8452_vector() {
+    let mut poly = SoftPolyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[test]
+fn update_reversed_computes_ghash() {
+    // GHASH(H, X_1, X_2) from RFC 8452 Appendix A
+    let ghash_result = hex!("bd9b3997046731fb96251b91f9c99d7a");
+
+    let mut h = H;
+    h.reverse();
+
+    let mut poly = Polyval::new(&polyval::mulx(&h.into()));
+    poly.update_reversed(&[X_1.into(), X_2.into()]);
+
+    let mut result = poly.finalize();
+    result.reverse();
+    assert_eq!(&ghash_result[..], result.as_slice());
+}
+
+#[test]
+fn finalize_with_order() {
+    // GHASH(H, X_1, X_2) from RFC 8452 Appendix A
+    let ghash_result = hex!("bd9b3997046731fb96251b91f9c99d7a");
+
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(
+        &POLYVAL_RESULT[..],
+        poly.finalize_with_order(ByteOrder::Polyval).as_slice()
+    );
+
+    let mut h = H;
+    h.reverse();
+
+    let mut poly = Polyval::new(&polyval::mulx(&h.into()));
+    poly.update_reversed(&[X_1.into(), X_2.into()]);
+    assert_eq!(
+        &ghash_result[..],
+        poly.finalize_with_order(ByteOrder::Ghash).as_slice()
+    );
+}
+
+#[test]
+fn clone_reset() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_2.into(), X_1.into()]);
+
+    let mut fresh = poly.clone_reset();
+    assert_eq!(fresh.block_count(), 0);
+
+    fresh.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], fresh.finalize().as_slice());
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn set_accumulator() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_1.into()]);
+    let checkpoint = poly.clone().finalize();
+
+    let mut resumed = Polyval::new(&H.into());
+    resumed.set_accumulator(&checkpoint);
+    resumed.update(&[X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], resumed.finalize().as_slice());
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn finalize_with_state() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    let (tag, state) = poly.finalize_with_state();
+    assert_eq!(&POLYVAL_RESULT[..], tag.as_slice());
+
+    let mut restored = Polyval::new(&H.into());
+    restored.set_accumulator(&state.into());
+    assert_eq!(restored.finalize(), tag);
+}
+
+#[test]
+fn finalize_xor() {
+    let mut hash = Polyval::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+
+    let mask = X_2.into();
+    let mut expected = hash.clone().finalize();
+    for (a, b) in expected.iter_mut().zip(X_2.iter()) {
+        *a ^= b;
+    }
+    assert_eq!(hash.finalize_xor(&mask), expected);
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn update_counted() {
+    let blocks = [X_1.into(); 9];
+    let mut hash = Polyval::new(&H.into());
+    let stats = hash.update_counted(&blocks);
+
+    // All backends process one block at a time
+    assert_eq!(stats.par_blocks_size, 1);
+    assert_eq!((stats.par_calls, stats.single_calls), (0, 9));
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update(&blocks);
+    assert_eq!(hash.finalize(), expected.finalize());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+    let mut hash = Polyval::new_with_init_block(&H.into(), init_block);
+    hash.update(&[X_1.into()]);
+
+    let json = serde_json::to_string(&hash).unwrap();
+    let mut restored: Polyval = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.block_count(), 1);
+
+    hash.update(&[X_2.into()]);
+    restored.update(&[X_2.into()]);
+    assert_eq!(hash.clone().finalize(), restored.clone().finalize());
+
+    // The initial block is also restored
+    hash.reset();
+    restored.reset();
+    assert_eq!(hash.finalize(), restored.finalize());
+}
+
+#[test]
+fn update_strided() {
+    // Blocks interleaved with 5 bytes of metadata each
+    let stride = 21;
+    let base: Vec<u8> = (0..=255).cycle().take(stride * 9).collect();
+
+    for count in [0, 1, 9] {
+        let mut strided = Polyval::new(&H.into());
+        strided.update_strided(&base, stride, count);
+
+        let blocks: Vec<_> = (0..count)
+            .map(|i| {
+                <[u8; 16]>::try_from(&base[i * stride..][..16])
+                    .unwrap()
+                    .into()
+            })
+            .collect();
+        let mut contiguous = Polyval::new(&H.into());
+        contiguous.update(&blocks);
+
+        assert_eq!(strided.finalize(), contiguous.finalize());
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn finalize_zeroizing() {
+    use core::mem::ManuallyDrop;
+
+    let mut hash = Polyval::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+
+    let mut tag = ManuallyDrop::new(hash.finalize_zeroizing());
+    assert_eq!(&POLYVAL_RESULT[..], tag.as_slice());
+
+    let ptr = &mut tag as *mut ManuallyDrop<_>;
+    unsafe {
+        ManuallyDrop::drop(&mut *ptr);
+        assert_eq!(*(ptr as *const [u8; 16]), [0u8; 16]);
+    }
+}
+
+#[test]
+fn update_raw_blocks() {
+    let mut raw = Polyval::new(&H.into());
+    raw.update_raw_blocks(&[X_1, X_2]);
+
+    let mut converted = Polyval::new(&H.into());
+    converted.update(&[X_1.into(), X_2.into()]);
+
+    assert_eq!(raw.finalize(), converted.finalize());
+}
+
+#[test]
+fn update_iter() {
+    let blocks: Vec<polyval::Block> = (0..=255u8)
+        .cycle()
+        .take(BLOCK_SIZE * 19)
+        .collect::<Vec<_>>()
+        .chunks(BLOCK_SIZE)
+        .map(|chunk| <[u8; BLOCK_SIZE]>::try_from(chunk).unwrap().into())
+        .collect();
+
+    for n in [0, 1, 7, 8, 9, 16, 19] {
+        let mut iter = Polyval::new(&H.into());
+        iter.update_iter(blocks[..n].iter().copied());
+
+        let mut slice = Polyval::new(&H.into());
+        slice.update(&blocks[..n]);
+
+        assert_eq!(iter.finalize(), slice.finalize());
+    }
+}
+
+#[test]
+fn tag_hex() {
+    use polyval::TagHex;
+
+    let tag = Polyval::hash_blocks(&H.into(), &[X_1.into(), X_2.into()]);
+    assert_eq!(
+        format!("{}", TagHex(&tag)),
+        "f7a3b47b846119fae5b7866cf5e5b77e"
+    );
+    assert_eq!(
+        format!("{:X}", TagHex(&tag)),
+        "F7A3B47B846119FAE5B7866CF5E5B77E"
+    );
+
+    // Formatting into a fixed-size buffer doesn't require `alloc`
+    struct Buf([u8; 32], usize);
+
+    impl core::fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.1 + s.len();
+            self.0
+                .get_mut(self.1..end)
+                .ok_or(core::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.1 = end;
+            Ok(())
+        }
+    }
+
+    let mut buf = Buf([0; 32], 0);
+    core::fmt::write(&mut buf, format_args!("{:x}", TagHex(&tag))).unwrap();
+    assert_eq!(&buf.0, b"f7a3b47b846119fae5b7866cf5e5b77e");
+}
+
+#[test]
+fn empty_tag() {
+    assert_eq!(
+        Polyval::empty_tag(&H.into()),
+        Polyval::new(&H.into()).finalize()
+    );
+}
+
+#[test]
+fn finalize_bytes() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(poly.clone().finalize_bytes(), poly.finalize().as_slice());
+}
+
+#[test]
+fn hash_blocks() {
+    let result = Polyval::hash_blocks(&H.into(), &[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], result.as_slice());
+}
+
+#[test]
+fn soft_polyval_matches_polyval() {
+    let data: Vec<u8> = xorshift_blocks(0x2545_f491_4f6c_dd1d, 64)
+        .flatten()
+        .collect();
+
+    for len in [0, 1, 16, 100, 1000] {
+        let key = &data[len..][..BLOCK_SIZE];
+        let mut soft = SoftPolyval::new(key.try_into().unwrap());
+        let mut poly = Polyval::new(key.try_into().unwrap());
+
+        soft.update_padded(&data[..len]);
+        poly.update_padded(&data[..len]);
+        assert_eq!(soft.finalize(), poly.finalize());
+    }
+}
+
+/// POLYVAL of a single block `a` under key `b` is the field product
+/// `a * b * x^-128`, so this compares the multiplication and reduction of each
+/// backend on pseudorandom field elements (plus edge cases which exercise all
+/// of the reduction's shifts).
+#[test]
+fn field_multiplication_matches_across_backends() {
+    let mut elements = vec![0, 1, u128::MAX, 1 << 127, (1 << 127) | 1, u64::MAX.into()];
+    elements.extend(
+        xorshift_blocks(0x9e37_79b9_7f4a_7c15, 64)
+            .map(|block| polyval::hazmat::block_to_u128_le(&block)),
+    );
+
+    for &a in &elements {
+        for &b in &elements {
+            let key = polyval::hazmat::block_from_u128_le(b);
+            let block = polyval::hazmat::block_from_u128_le(a);
+
+            let mut soft = SoftPolyval::new(&key);
+            soft.update(&[block]);
+            let expected = soft.finalize();
+
+            let mut poly = Polyval::new(&key);
+            poly.update(&[block]);
+            assert_eq!(poly.finalize(), expected, "a = {a:#x}, b = {b:#x}");
+
+            #[cfg(feature = "table-soft")]
+            {
+                let mut table = polyval::TablePolyval::new(&key);
+                table.update(&[block]);
+                assert_eq!(table.finalize(), expected, "a = {a:#x}, b = {b:#x}");
+            }
+        }
+    }
+}
+
+#[test]
+fn update_length_prefixed() {
+    let mut prefixed = [0u8; 32];
+    prefixed[7] = 5;
+    prefixed[16..21].copy_from_slice(b"hello");
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update_padded(&prefixed);
+
+    let mut poly = Polyval::new(&H.into());
+    poly.update_length_prefixed(b"hello");
+    assert_eq!(poly.finalize(), expected.finalize());
+
+    // Without the prefix these inputs are indistinguishable after padding
+    let mut short = Polyval::new(&H.into());
+    short.update_length_prefixed(b"hello");
+
+    let mut long = Polyval::new(&H.into());
+    long.update_length_prefixed(b"hello\0");
+    assert_ne!(short.finalize(), long.finalize());
+}
+
+#[test]
+fn update_block() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update_block(&X_1.into());
+    poly.update_block(&X_2.into());
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[test]
+fn update_u128() {
+    let mut poly = Polyval::new(&H.into());
+    poly.update_u128(u128::from_le_bytes(X_1));
+    poly.update_u128(u128::from_le_bytes(X_2));
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[test]
+fn update_padded_in() {
+    let data: Vec<u8> = (0..=255).cycle().take(100).collect();
+
+    for len in [0, 1, 15, 16, 17, 100] {
+        let mut buf = [0xffu8; 112];
+        buf[..len].copy_from_slice(&data[..len]);
+
+        let mut poly = Polyval::new(&H.into());
+        poly.update_padded_in(&mut buf, len);
+
+        let mut expected = Polyval::new(&H.into());
+        expected.update_padded(&data[..len]);
+        assert_eq!(poly.finalize(), expected.finalize());
+        assert!(buf[len..len.div_ceil(16) * 16].iter().all(|&b| b == 0));
+    }
+}
+
+#[test]
+#[should_panic]
+fn update_padded_in_short_buffer() {
+    let mut buf = [0u8; 20];
+    Polyval::new(&H.into()).update_padded_in(&mut buf, 17);
+}
+
+/// With `CLMUL` statically enabled, the `CLMUL` backend is used directly
+/// rather than via runtime detection.
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    target_feature = "pclmulqdq",
+    not(any(polyval_force_soft, miri))
+))]
+#[test]
+fn static_clmul_backend() {
+    assert!(polyval::detected_capabilities().clmul);
+
+    let mut poly = Polyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[test]
+fn finalize_partial() {
+    for len in [0, 1, 15] {
+        let mut expected = Polyval::new(&H.into());
+        expected.update(&[X_1.into()]);
+        expected.update_padded(&X_2[..len]);
+
+        let mut hash = Polyval::new(&H.into());
+        hash.update(&[X_1.into()]);
+        assert_eq!(hash.finalize_partial(&X_2[..len]), expected.finalize());
+    }
+}
+
+#[test]
+#[should_panic]
+fn finalize_partial_full_block() {
+    let _ = Polyval::new(&H.into()).finalize_partial(&X_1);
+}
+
+#[test]
+fn new_from_array() {
+    let mut from_array = Polyval::new_from_array(&H);
+    from_array.update(&[X_1.into(), X_2.into()]);
+
+    let mut from_key = Polyval::new(&H.into());
+    from_key.update(&[X_1.into(), X_2.into()]);
+
+    assert_eq!(&POLYVAL_RESULT[..], from_array.finalize().as_slice());
+    assert_eq!(&POLYVAL_RESULT[..], from_key.finalize().as_slice());
+}
+
+#[test]
+fn finalize_truncated() {
+    let mut hash = Polyval::new(&H.into());
+    hash.update(&[X_1.into(), X_2.into()]);
+    let tag = hash.clone().finalize();
+
+    for len in [0, 8, 12, 16, 20] {
+        let mut out = [0xff; 20];
+        hash.clone().finalize_truncated(&mut out[..len]);
+
+        let n = len.min(16);
+        assert_eq!(out[..n], tag[..n]);
+        assert!(out[n..].iter().all(|&b| b == 0xff));
+    }
+}
+
+#[test]
+fn absorb_padded() {
+    let data: Vec<u8> = (0..48).collect();
+
+    for len in [0, 1, 15, 16, 17, 48] {
+        let mut expected = Polyval::new(&H.into());
+        expected.update_padded(&data[..len]);
+        expected.update_padded(&data[..len]);
+
+        let tag = Polyval::new(&H.into())
+            .absorb_padded(&data[..len])
+            .absorb_padded(&data[..len])
+            .finalize();
+
+        assert_eq!(tag, expected.finalize());
+    }
+}
+
+#[test]
+fn update_padded_with() {
+    let data: Vec<u8> = (0..48).collect();
+
+    for len in [0, 1, 15, 16, 17, 32, 48] {
+        let mut expected = Polyval::new(&H.into());
+        expected.update_padded(&data[..len]);
+
+        let mut poly = Polyval::new(&H.into());
+        poly.update_padded_with(&data[..len], 0, false);
+        assert_eq!(poly.finalize(), expected.finalize());
+    }
+
+    // `10*` padding
+    let mut padded = [0u8; 32];
+    padded[..17].copy_from_slice(&data[..17]);
+    padded[17] = 0x80;
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update_padded(&padded);
+
+    let mut poly = Polyval::new(&H.into());
+    poly.update_padded_with(&data[..17], 0x80, true);
+    assert_eq!(poly.finalize(), expected.finalize());
+
+    let mut padded = [0u8; 32];
+    padded[..16].copy_from_slice(&data[..16]);
+    padded[16] = 0x80;
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update_padded(&padded);
+
+    let mut poly = Polyval::new(&H.into());
+    poly.update_padded_with(&data[..16], 0x80, true);
+    assert_eq!(poly.finalize(), expected.finalize());
+}
+
+/// AEAD_AES_128_GCM_SIV worked examples from RFC 8452 Appendix C.1
+#[test]
+fn polyval_siv() {
+    let auth_key = hex!("d9b360279694941ac5dbc6987ada7377");
+
+    assert_eq!(
+        polyval::siv::polyval_siv(&auth_key.into(), &[], &[]).as_slice(),
+        [0u8; BLOCK_SIZE]
+    );
+    assert_eq!(
+        polyval::siv::polyval_siv(&auth_key.into(), &[], &hex!("0100000000000000")).as_slice(),
+        hex!("eb93b7740962c5e49d2a90a7dc5cec74")
+    );
+}
+
+/// Key derivation examples from RFC 8452 Appendix C.1 and C.2
+#[test]
+fn siv_derive_keys() {
+    let nonce = hex!("030000000000000000000000");
+
+    // Stand-in for AES under the key-generating key `01000000...` which only
+    // supports the blocks key derivation encrypts.
+    let encrypt_block = |outputs: &'static [[u8; BLOCK_SIZE]]| {
+        move |block: &mut polyval::Block| {
+            let i = u32::from_le_bytes(block[..4].try_into().unwrap()) as usize;
+            assert_eq!(block[4..], nonce);
+            block.copy_from_slice(&outputs[i]);
+        }
+    };
+
+    // AES-128
+    let (auth_key, enc_key) = polyval::siv::derive_keys::<16>(
+        encrypt_block(&[
+            hex!("d9b360279694941a2010be790ff81954"),
+            hex!("c5dbc6987ada737758568e3552059132"),
+            hex!("4004a0dcd862f2a5f559f116bee72388"),
+            hex!("7360219d2d44ef6c3d190c795fe54c0c"),
+        ]),
+        &nonce,
+    );
+    assert_eq!(
+        auth_key.as_slice(),
+        hex!("d9b360279694941ac5dbc6987ada7377")
+    );
+    assert_eq!(enc_key, hex!("4004a0dcd862f2a57360219d2d44ef6c"));
+
+    // AES-256
+    let (auth_key, enc_key) = polyval::siv::derive_keys::<32>(
+        encrypt_block(&[
+            hex!("b5d3c529dfafac430384a63b046e999e"),
+            hex!("136d2d11be284d7fd498b142a2995a5a"),
+            hex!("b914f4742be9e1d7641d397a9a0ffa64"),
+            hex!("a2f84addbf96dec3fcdefbceafd2a5f3"),
+            hex!("456e3c6c05ecc15709d8894632480faf"),
+            hex!("cdbf0700fedad22273098dd2eaa268b3"),
+        ]),
+        &nonce,
+    );
+    assert_eq!(
+        auth_key.as_slice(),
+        hex!("b5d3c529dfafac43136d2d11be284d7f")
+    );
+    assert_eq!(
+        enc_key,
+        hex!("b914f4742be9e1d7a2f84addbf96dec3456e3c6c05ecc157cdbf0700fedad222")
+    );
+}
+
+#[test]
+fn streaming_mac_snapshots() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut mac = StreamingMac::<Polyval>::new(&H.into());
+
+    for (i, chunk) in data.chunks(BLOCK_SIZE).enumerate() {
+        mac.push(chunk);
+
+        let mut expected = Polyval::new(&H.into());
+        expected.update_padded(&data[..((i + 1) * BLOCK_SIZE)]);
+        assert_eq!(mac.snapshot(), expected.finalize());
+    }
+
+    // Input which doesn't fall on block boundaries
+    mac.reset_keep_key();
+
+    for chunk in data.chunks(7) {
+        mac.push(chunk);
+    }
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update_padded(&data);
+    assert_eq!(mac.snapshot(), expected.finalize());
+}
+
+#[test]
+fn auth_key() {
+    let key = AuthKey::from(H);
+    assert_eq!(format!("{:?}", key), "AuthKey(***)");
+
+    let mut poly = Polyval::new_from_authkey(&key);
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn auth_key_zeroized_on_drop() {
+    use core::mem::ManuallyDrop;
+
+    let mut key = ManuallyDrop::new(AuthKey::from(H));
+    let ptr: *mut ManuallyDrop<AuthKey> = &mut key;
+    unsafe {
+        ManuallyDrop::drop(&mut *ptr);
+        assert_eq!(*(ptr as *const [u8; BLOCK_SIZE]), [0u8; BLOCK_SIZE]);
+    }
+}
+
+#[test]
+fn reset_restores_init_block() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    let mut poly = Polyval::new_with_init_block(&H.into(), init_block);
+    poly.update(&[X_1.into()]);
+    poly.reset();
+    poly.update(&[X_2.into()]);
+
+    let mut expected = Polyval::new_with_init_block(&H.into(), init_block);
+    expected.update(&[X_2.into()]);
+    assert_eq!(poly.finalize(), expected.finalize());
+}
+
+#[test]
+fn reset_with_init_block() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    let mut hash = Polyval::new(&H.into());
+    hash.update(&[X_1.into()]);
+    hash.reset_with_init_block(init_block);
+    hash.update(&[X_2.into()]);
+
+    let mut expected = Polyval::new_with_init_block(&H.into(), init_block);
+    expected.update(&[X_2.into()]);
+    assert_eq!(hash.clone().finalize(), expected.clone().finalize());
+
+    // Subsequent resets restore the new initial block
+    hash.reset();
+    expected.reset();
+    assert_eq!(hash.finalize(), expected.finalize());
+
+    let mut soft = SoftPolyval::new(&H.into());
+    soft.reset_with_init_block(init_block);
+    assert_eq!(
+        soft.finalize(),
+        Polyval::new_with_init_block(&H.into(), init_block).finalize()
+    );
+}
+
+#[test]
+fn block_u128_round_trip() {
+    let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+    let block = polyval::hazmat::block_from_u128_le(x);
+    assert_eq!(block.as_slice(), x.to_le_bytes());
+    assert_eq!(polyval::hazmat::block_to_u128_le(&block), x);
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn gf_mul() {
+    use polyval::hazmat::gf_mul;
+
+    // x^128 mod the POLYVAL polynomial, i.e. the multiplicative identity of `dot`
+    let one = hex!("010000000000000000000000000000c2").into();
+
+    for x in [H, X_1, X_2] {
+        let x = x.into();
+        assert_eq!(gf_mul(&x, &one), x);
+        assert_eq!(gf_mul(&one, &x), x);
+
+        let mut soft = SoftPolyval::new(&H.into());
+        soft.update(&[x]);
+        assert_eq!(gf_mul(&x, &H.into()), soft.finalize());
+    }
+
+    // POLYVAL(H, X_1, X_2) = dot(dot(X_1, H) + X_2, H)
+    let mut acc = gf_mul(&X_1.into(), &H.into());
+    for (a, b) in acc.iter_mut().zip(X_2.iter()) {
+        *a ^= b;
+    }
+    assert_eq!(&POLYVAL_RESULT[..], gf_mul(&acc, &H.into()).as_slice());
+}
+
+#[cfg(feature = "hazmat")]
+#[test]
+fn gf_mul_unreduced() {
+    use polyval::hazmat::{gf_mul, gf_mul_unreduced, gf_reduce};
+
+    // x^127 * x^127 = x^254
+    let mut x127 = polyval::Block::default();
+    x127[15] = 0x80;
+    let [lo, hi] = gf_mul_unreduced(&x127, &x127);
+    assert_eq!(lo, polyval::Block::default());
+    assert_eq!(hi.as_slice(), hex!("00000000000000000000000000000040"));
+
+    // Aggregated reduction: sum the unreduced products, then reduce once
+    let pairs = [(X_1, H), (X_2, X_1), (H, X_2)];
+    let mut unreduced = [polyval::Block::default(); 2];
+    let mut expected = polyval::Block::default();
+
+    for (a, b) in pairs {
+        let (a, b) = (a.into(), b.into());
+        for (acc, half) in unreduced.iter_mut().zip(gf_mul_unreduced(&a, &b)) {
+            for (x, y) in acc.iter_mut().zip(half) {
+                *x ^= y;
+            }
+        }
+        for (x, y) in expected.iter_mut().zip(gf_mul(&a, &b)) {
+            *x ^= y;
+        }
+    }
+
+    assert_eq!(gf_reduce(&unreduced), expected);
+}
+
+#[test]
+fn init_block_consistent_across_backends() {
+    let init_block = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+
+    let poly = Polyval::new_with_init_block(&H.into(), init_block);
+    let soft = SoftPolyval::new_with_init_block(&H.into(), init_block);
+    assert_eq!(
+        polyval::hazmat::block_to_u128_le(&poly.clone().finalize()),
+        init_block
+    );
+    assert_eq!(poly.clone().finalize(), soft.clone().finalize());
+
+    let mut poly = poly;
+    let mut soft = soft;
+    poly.update(&[X_1.into(), X_2.into()]);
+    soft.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(poly.finalize(), soft.finalize());
+}
+
+#[test]
+fn block_count() {
+    let mut poly = Polyval::new(&H.into());
+    assert_eq!(poly.block_count(), 0);
+
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(poly.block_count(), 2);
+
+    poly.update_padded(&[0u8; 17]);
+    assert_eq!(poly.block_count(), 4);
+
+    poly.reset();
+    assert_eq!(poly.block_count(), 0);
+}
+
+#[test]
+fn padded_len() {
+    let mut poly = Polyval::new(&H.into());
+    assert!(poly.is_empty());
+    assert_eq!(poly.padded_len(), 0);
+
+    poly.update_padded(&[]);
+    assert!(poly.is_empty());
+
+    poly.update(&[X_1.into()]);
+    assert!(!poly.is_empty());
+    assert_eq!(poly.padded_len(), 16);
+
+    poly.update_padded(&[0u8; 17]);
+    assert_eq!(poly.padded_len(), 48);
+
+    poly.reset();
+    assert!(poly.is_empty());
+    assert_eq!(poly.padded_len(), 0);
+}
+
+#[cfg(feature = "table-soft")]
+#[test]
+fn table_polyval_test_vector() {
+    let mut poly = polyval::TablePolyval::new(&H.into());
+    poly.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], poly.finalize().as_slice());
+}
+
+#[cfg(feature = "table-soft")]
+#[test]
+fn table_polyval_matches_polyval() {
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    for key in [[0u8; BLOCK_SIZE], [0xff; BLOCK_SIZE], H, X_1, X_2] {
+        for len in [0, 1, 15, 16, 17, 255, 1000] {
+            let mut table = polyval::TablePolyval::new(&key.into());
+            let mut poly = Polyval::new(&key.into());
+            table.update_padded(&data[..len]);
+            poly.update_padded(&data[..len]);
+            assert_eq!(table.clone().finalize(), poly.clone().finalize());
+
+            table.reset();
+            poly.reset();
+            table.update_padded(&data[len..]);
+            poly.update_padded(&data[len..]);
+            assert_eq!(table.finalize(), poly.finalize());
+        }
+    }
+}
+
+/// Generic code can use the traits re-exported at the crate root as bounds.
+#[test]
+fn root_reexports() {
+    fn sizes<T: BlockSizeUser + KeySizeUser>() -> (usize, usize) {
+        (T::block_size(), T::key_size())
+    }
+
+    assert_eq!(sizes::<Polyval>(), (16, 16));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_universal_hash() {
+    let mut hashes: Vec<Box<dyn polyval::DynUniversalHash>> = vec![
+        Box::new(Polyval::new(&H.into())),
+        Box::new(Polyval::new_with_init_block(&H.into(), 1)),
+    ];
+
+    for hash in &mut hashes {
+        hash.dyn_update(&[X_1.into()]);
+        hash.dyn_update_padded(&X_2);
+    }
+
+    let tags: Vec<_> = hashes.into_iter().map(|h| h.finalize_boxed()).collect();
+    assert_eq!(&POLYVAL_RESULT[..], tags[0].as_slice());
+    assert_ne!(tags[0], tags[1]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn compute_chain() {
+    let data: Vec<u8> = (0..=255).cycle().take(100).collect();
+    let chunks = [
+        &data[..7],
+        &data[7..16],
+        &data[16..16],
+        &data[16..50],
+        &data[50..],
+    ];
+    let tags = Polyval::compute_chain(&H.into(), &chunks);
+    assert_eq!(tags.len(), chunks.len());
+
+    let mut end = 0;
+    for (chunk, tag) in chunks.iter().zip(&tags) {
+        end += chunk.len();
+        let mut poly = Polyval::new(&H.into());
+        poly.update_padded(&data[..end]);
+        assert_eq!(&poly.finalize(), tag);
+    }
+}
+
+#[test]
+fn verify_streamed() {
+    let buf = [X_1, X_2, POLYVAL_RESULT].concat();
+    let hash = Polyval::new(&H.into());
+    assert_eq!(hash.clone().verify_streamed(&buf), Ok(&buf[..32]));
+
+    let mut tampered = buf.clone();
+    tampered[0] ^= 1;
+    assert!(hash.clone().verify_streamed(&tampered).is_err());
+
+    let mut bad_tag = buf.clone();
+    bad_tag[47] ^= 1;
+    assert!(hash.clone().verify_streamed(&bad_tag).is_err());
+
+    assert!(hash.verify_streamed(&buf[..15]).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn verify_batch() {
+    let hash = polyval::Polyval::new(&H.into());
+    let msg_a = [X_1, X_2].concat();
+    let msg_b = b"short message";
+    let mut hasher = hash.clone_reset();
+    hasher.update(&[X_1.into(), X_2.into()]);
+    let tag_a = hasher.finalize();
+
+    let mut hasher = hash.clone_reset();
+    hasher.update_padded(msg_b);
+    let tag_b = hasher.finalize();
+    let mut tampered = msg_a.clone();
+    tampered[3] ^= 1;
+
+    let items: [(&[u8], _); 4] = [
+        (&msg_a, &tag_a),
+        (&tampered, &tag_a),
+        (msg_b, &tag_b),
+        (msg_b, &tag_a),
+    ];
+    assert_eq!(hash.verify_batch(&items), Err(vec![1, 3]));
+    assert_eq!(hash.verify_batch(&[items[0], items[2]]), Ok(()));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_key() {
+    let mut key = polyval::Key::from(H);
+    polyval::zeroize_key(&mut key);
+    assert_eq!(key.as_slice(), [0u8; BLOCK_SIZE]);
+}
+
+/// Guard against performance regressions which disable the intrinsics
+/// backends. Only meaningful in release builds, e.g.
+/// `RUSTFLAGS="--cfg polyval_bench_guard" cargo test --release`.
+#[cfg(polyval_bench_guard)]
+#[test]
+fn throughput_floor() {
+    use std::time::Instant;
+
+    /// Conservative lower bound for the CLMUL/PMULL backends, which is well
+    /// above the throughput of the "soft" backend.
+    const MIN_MIB_PER_SEC: f64 = 750.0;
+
+    let caps = polyval::detected_capabilities();
+    if cfg!(debug_assertions) || !(caps.clmul || caps.pmull) {
+        return;
+    }
+
+    let data = vec![0x42u8; 1 << 20];
+
+    // Take the best of several runs to reduce flakiness
+    let best = (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            let mut poly = Polyval::new(&H.into());
+            poly.update_padded(&data);
+            std::hint::black_box(poly.finalize());
+            start.elapsed().as_secs_f64()
+        })
+        .fold(f64::INFINITY, f64::min);
+
+    let mib_per_sec = 1.0 / best;
+    assert!(
+        mib_per_sec >= MIN_MIB_PER_SEC,
+        "POLYVAL throughput {mib_per_sec:.0} MiB/s is below {MIN_MIB_PER_SEC} MiB/s"
+    );
+}
+
+#[cfg(feature = "hex")]
+#[test]
+fn from_hex_key() {
+    use polyval::base16ct::Error;
+
+    for s in [
+        "25629347589242761d31f826ba4b757b",
+        "25629347589242761D31F826BA4B757B",
+    ] {
+        let mut hash = Polyval::from_hex_key(s).unwrap();
+        hash.update(&[X_1.into(), X_2.into()]);
+        assert_eq!(&POLYVAL_RESULT[..], hash.finalize().as_slice());
+    }
+
+    assert!(matches!(
+        Polyval::from_hex_key("25629347589242761d31f826ba4b757g"),
+        Err(Error::InvalidEncoding)
+    ));
+    assert!(matches!(
+        Polyval::from_hex_key("25629347589242761d31f826ba4b75"),
+        Err(Error::InvalidLength)
+    ));
+    assert!(matches!(
+        Polyval::from_hex_key("25629347589242761d31f826ba4b757b00"),
+        Err(Error::InvalidLength)
+    ));
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn new_from_secret() {
+    use polyval::secrecy::SecretBox;
+
+    let key = SecretBox::new(Box::new(polyval::Key::from(H)));
+    let mut hash = Polyval::new_from_secret(&key);
+    hash.update(&[X_1.into(), X_2.into()]);
+    assert_eq!(&POLYVAL_RESULT[..], hash.finalize().as_slice());
+}
+
+#[test]
+fn update_with_progress() {
+    let data = vec![0x42u8; 3 * 65536 + 5];
+    let mut progress = Vec::new();
+
+    let mut hash = Polyval::new(&H.into());
+    hash.update_with_progress(&data, |n| progress.push(n));
+    assert_eq!(progress, [65536, 2 * 65536, 3 * 65536, data.len()]);
+
+    let mut expected = Polyval::new(&H.into());
+    expected.update_padded(&data);
+    assert_eq!(hash.finalize(), expected.finalize());
+}
+
+/// dudect-style statistical timing test for the "soft" backend's constant-time
+/// multiplication: compares the time taken to process a fixed class of inputs
+/// (all zeroes) against a class of pseudorandom inputs using Welch's t-test,
+/// first varying the input blocks and then the key.
+///
+/// Only meaningful in release builds, e.g.
+/// `RUSTFLAGS="--cfg polyval_ct_tests" cargo test --release`. A failure indicates the time
+/// taken depends on the data (i.e. a timing leak) and should be investigated,
+/// although it can occasionally be caused by a very noisy machine.
+#[cfg(polyval_ct_tests)]
+#[test]
+fn soft_multiply_constant_time() {
+    use std::{hint::black_box, time::Instant};
+
+    /// Threshold for |t| above which timings are considered data dependent
+    /// (the same as dudect's "definitely not constant time" threshold)
+    const T_THRESHOLD: f64 = 10.0;
+    const SAMPLES: usize = 100_000;
+    const BLOCKS_PER_SAMPLE: usize = 16;
+
+    type Sample = (bool, polyval::Key, [polyval::Block; BLOCKS_PER_SAMPLE]);
+
+    if cfg!(debug_assertions) {
+        return;
+    }
+
+    // Pseudorandom inputs and class selection
+    let mut random = xorshift_blocks(0x9e37_79b9_7f4a_7c15, usize::MAX);
+    let mut random_block = move || random.next().unwrap();
+
+    let key = random_block();
+    let blocks: [polyval::Block; BLOCKS_PER_SAMPLE] = core::array::from_fn(|_| random_block());
+
+    let input_samples: Vec<Sample> = (0..SAMPLES)
+        .map(|_| match random_block()[0] & 1 {
+            0 => (false, key, Default::default()),
+            _ => (true, key, core::array::from_fn(|_| random_block())),
+        })
+        .collect();
+
+    let key_samples: Vec<Sample> = (0..SAMPLES)
+        .map(|_| match random_block()[0] & 1 {
+            0 => (false, Default::default(), blocks),
+            _ => (true, random_block(), blocks),
+        })
+        .collect();
+
+    for (name, samples) in [("input", input_samples), ("key", key_samples)] {
+        let mut timings: Vec<(bool, f64)> = samples
+            .iter()
+            .map(|(class, key, blocks)| {
+                let start = Instant::now();
+                let mut poly = SoftPolyval::new(black_box(key));
+                poly.update(black_box(blocks));
+                black_box(poly.finalize());
+                (*class, start.elapsed().as_nanos() as f64)
+            })
+            .collect();
+
+        // Discard outliers (e.g. caused by interrupts) above the 90th percentile
+        let mut sorted: Vec<f64> = timings.iter().map(|&(_, t)| t).collect();
+        sorted.sort_by(f64::total_cmp);
+        let cutoff = sorted[sorted.len() * 9 / 10];
+        timings.retain(|&(_, t)| t <= cutoff);
+
+        let stats = |class: bool| {
+            let xs: Vec<f64> = timings
+                .iter()
+                .filter(|&&(c, _)| c == class)
+                .map(|&(_, t)| t)
+                .collect();
+            let n = xs.len() as f64;
+            let mean = xs.iter().sum::<f64>() / n;
+            let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, var, n)
+        };
+
+        let (mean0, var0, n0) = stats(false);
+        let (mean1, var1, n1) = stats(true);
+        let t = (mean0 - mean1) / (var0 / n0 + var1 / n1).sqrt();
+
+        assert!(
+            t.abs() < T_THRESHOLD,
+            "possible timing leak varying {name}: t = {t:.2} \
+             (fixed class mean {mean0:.1}ns, random class mean {mean1:.1}ns)"
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod glob_import {
+    use polyval::*;
+
+    #[test]
+    fn update_is_unambiguous() {
+        let mut polyval = Polyval::new(&super::H.into());
+        polyval.update(&[super::X_1.into()]);
+        polyval.update_padded(&super::X_2);
+        assert_eq!(&super::POLYVAL_RESULT[..], polyval.finalize().as_slice());
+    }
+}